@@ -54,5 +54,6 @@ func main() {
 
 	print_loop();
 
-	print(custom_mul(17, 4));
+	print_int(custom_mul(17, 4));
+	print('\n');
 }
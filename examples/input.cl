@@ -0,0 +1,17 @@
+/// Prints the Sum of all the Numbers on stdin
+func main() {
+	number sum = 0;
+	number count = 0;
+	number value = read_int();
+	while (!eof()) {
+		sum = sum + value;
+		count = count + 1;
+		value = read_int();
+	}
+
+	print("read ");
+	print_int(count);
+	print(" numbers, sum: ");
+	print_int(sum);
+	print('\n');
+}
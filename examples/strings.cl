@@ -0,0 +1,22 @@
+/// Returns the Greeting for the Language
+func greeting(formal: bool) -> string {
+	if (formal) {
+		return "Good day, World!\n";
+	}
+
+	return "hello, world\n";
+}
+
+func print_twice(text: string) {
+	print(text);
+	print(text);
+}
+
+func main() {
+	string message = greeting(false);
+	print(message);
+	print_twice(greeting(true));
+	print("\tdone");
+	print('\x21');
+	print('\n');
+}
@@ -1,5 +1,7 @@
 use std::collections::BTreeMap;
 
+use crate::frontend::ir::IRType;
+
 // Structure
 //
 // Local-Variables:
@@ -9,6 +11,14 @@ use std::collections::BTreeMap;
 pub mod archs;
 pub mod traits;
 
+/// The Location and Type of a single Variable on the Stack
+#[derive(Debug, PartialEq, Clone)]
+pub struct Variable {
+    /// The Offset below the Stack-Base-Pointer
+    pub offset: i64,
+    pub var_type: IRType,
+}
+
 /// Stores the Stack-Offset for every variable
 /// in the current Scope
-pub type VariableOffsets = BTreeMap<String, i64>;
+pub type VariableOffsets = BTreeMap<String, Variable>;
@@ -1,3 +1,6 @@
+use std::fmt;
+
+#[allow(clippy::upper_case_acronyms, dead_code)]
 #[derive(Debug, PartialEq)]
 pub enum Register {
     RAX,
@@ -12,21 +15,48 @@ pub enum Register {
     RDI,
 }
 
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Register::RAX => "rax",
+            Register::RBX => "rbx",
+            Register::RCX => "rcx",
+            Register::RDX => "rdx",
+            Register::RSP => "rsp",
+            Register::RBP => "rbp",
+            Register::RSI => "rsi",
+            Register::RDI => "rdi",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
 impl Register {
-    pub fn to_string(&self) -> String {
-        match self {
-            Register::RAX => "rax".to_string(),
-            Register::RBX => "rbx".to_string(),
-            Register::RCX => "rcx".to_string(),
-            Register::RDX => "rdx".to_string(),
-            Register::RSP => "rsp".to_string(),
-            Register::RBP => "rbp".to_string(),
-            Register::RSI => "rsi".to_string(),
-            Register::RDI => "rdi".to_string(),
+    /// The Name of the lower Part of the Register with the
+    /// given Size in Bytes, like 'eax' for 4 Bytes of 'rax'
+    pub fn sized(&self, size: usize) -> &'static str {
+        let names = match self {
+            Register::RAX => ["al", "ax", "eax", "rax"],
+            Register::RBX => ["bl", "bx", "ebx", "rbx"],
+            Register::RCX => ["cl", "cx", "ecx", "rcx"],
+            Register::RDX => ["dl", "dx", "edx", "rdx"],
+            Register::RSP => ["spl", "sp", "esp", "rsp"],
+            Register::RBP => ["bpl", "bp", "ebp", "rbp"],
+            Register::RSI => ["sil", "si", "esi", "rsi"],
+            Register::RDI => ["dil", "di", "edi", "rdi"],
+        };
+
+        match size {
+            1 => names[0],
+            2 => names[1],
+            4 => names[2],
+            _ => names[3],
         }
     }
 }
 
+#[allow(dead_code)]
 #[derive(Debug, PartialEq)]
 pub enum Instruction {
     Comment(String),
@@ -34,21 +64,56 @@ pub enum Instruction {
     Label(String),
     Add(String, String),
     Sub(String, String),
+    Imul(String, String),
+    Idiv(String),
+    /// Unsigned Division
+    Div(String),
+    Neg(String),
+    Cqo,
     Xor(String, String),
     Move(String, String),
+    /// Moves with Sign-Extension
+    Movsx(String, String),
+    /// Moves a 32-Bit Value with Sign-Extension
+    Movsxd(String, String),
+    /// Moves with Zero-Extension
+    Movzx(String, String),
     Return,
     Push(String),
     Pop(String),
     Call(String),
     Cmp(String, String),
+    Jmp(String),
     Je(String),
     Jne(String),
+    Jl(String),
     Jle(String),
     Jg(String),
+    Jge(String),
+    /// The Jumps for unsigned Comparisons
+    Jb(String),
+    Jbe(String),
+    Ja(String),
+    Jae(String),
+    Sete(String),
+    Setne(String),
+    Setl(String),
+    Setle(String),
+    Setg(String),
+    Setge(String),
+    /// The Sets for unsigned Comparisons
+    Setb(String),
+    Setbe(String),
+    Seta(String),
+    Setae(String),
     Syscall,
     Int(String),
     Lea(String, String),
     Raw(String),
+    /// Raw Bytes in a Data-Section
+    Bytes(Vec<u8>),
+    /// Uninitialized Bytes in the BSS-Section
+    Reserve(usize),
 }
 
 fn format_asm(instr: &Instruction) -> String {
@@ -58,29 +123,57 @@ fn format_asm(instr: &Instruction) -> String {
         Instruction::Label(a1) => format!("  {}:", a1),
         Instruction::Add(a1, a2) => format!("    add {}, {}", a1, a2),
         Instruction::Sub(a1, a2) => format!("    sub {}, {}", a1, a2),
+        Instruction::Imul(a1, a2) => format!("    imul {}, {}", a1, a2),
+        Instruction::Idiv(a1) => format!("    idiv {}", a1),
+        Instruction::Div(a1) => format!("    div {}", a1),
+        Instruction::Neg(a1) => format!("    neg {}", a1),
+        Instruction::Cqo => "    cqo".to_string(),
         Instruction::Xor(a1, a2) => format!("    xor {}, {}", a1, a2),
         Instruction::Move(a1, a2) => format!("    mov {}, {}", a1, a2),
-        Instruction::Return => format!("    ret"),
+        Instruction::Movsx(a1, a2) => format!("    movsx {}, {}", a1, a2),
+        Instruction::Movsxd(a1, a2) => format!("    movsxd {}, {}", a1, a2),
+        Instruction::Movzx(a1, a2) => format!("    movzx {}, {}", a1, a2),
+        Instruction::Return => "    ret".to_string(),
         Instruction::Push(a1) => format!("    push {}", a1),
         Instruction::Pop(a1) => format!("    pop {}", a1),
         Instruction::Call(a1) => format!("    call {}", a1),
         Instruction::Cmp(a1, a2) => format!("    cmp {}, {}", a1, a2),
+        Instruction::Jmp(a1) => format!("    jmp {}", a1),
         Instruction::Je(a1) => format!("    je {}", a1),
         Instruction::Jne(a1) => format!("    jne {}", a1),
+        Instruction::Jl(a1) => format!("    jl {}", a1),
         Instruction::Jle(a1) => format!("    jle {}", a1),
         Instruction::Jg(a1) => format!("    jg {}", a1),
-        Instruction::Syscall => format!("    syscall"),
+        Instruction::Jge(a1) => format!("    jge {}", a1),
+        Instruction::Jb(a1) => format!("    jb {}", a1),
+        Instruction::Jbe(a1) => format!("    jbe {}", a1),
+        Instruction::Ja(a1) => format!("    ja {}", a1),
+        Instruction::Jae(a1) => format!("    jae {}", a1),
+        Instruction::Sete(a1) => format!("    sete {}", a1),
+        Instruction::Setne(a1) => format!("    setne {}", a1),
+        Instruction::Setl(a1) => format!("    setl {}", a1),
+        Instruction::Setle(a1) => format!("    setle {}", a1),
+        Instruction::Setg(a1) => format!("    setg {}", a1),
+        Instruction::Setge(a1) => format!("    setge {}", a1),
+        Instruction::Setb(a1) => format!("    setb {}", a1),
+        Instruction::Setbe(a1) => format!("    setbe {}", a1),
+        Instruction::Seta(a1) => format!("    seta {}", a1),
+        Instruction::Setae(a1) => format!("    setae {}", a1),
+        Instruction::Syscall => "    syscall".to_string(),
         Instruction::Int(a1) => format!("    int {}", a1),
         Instruction::Lea(a1, a2) => format!("    lea {},{}", a1, a2),
-        Instruction::Raw(a1) => format!("{}", a1),
+        Instruction::Raw(a1) => a1.to_string(),
+        Instruction::Bytes(bytes) => format!(
+            "    db {}",
+            bytes
+                .iter()
+                .map(|b| b.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        Instruction::Reserve(size) => format!("    resb {}", size),
     }
 }
-pub fn pretty_print(instructions: &[Instruction]) {
-    for instr in instructions.iter() {
-        println!("{}", format_asm(instr));
-    }
-}
-
 pub fn format(instructions: &[Instruction]) -> String {
     let mut result = String::new();
 
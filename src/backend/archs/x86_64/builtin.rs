@@ -2,11 +2,74 @@ use super::asm::Instruction;
 
 pub mod helper;
 mod print;
+mod read;
 
 pub fn generate_builtins() -> Vec<Instruction> {
     let mut result = Vec::new();
 
     print::generate(&mut result);
+    print::generate_string(&mut result);
+    print::generate_int(&mut result);
+    read::generate_char(&mut result);
+    read::generate_int(&mut result);
+    read::generate_eof(&mut result);
 
     result
 }
+
+/// The Memory that is used by the Builtins at runtime, which belongs
+/// into the BSS-Section
+pub fn generate_data() -> Vec<Instruction> {
+    read::generate_data()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        backend::archs::x86_64::asm::Register,
+        frontend::sema::{is_assembly_keyword, RESERVED_NAMES},
+    };
+
+    #[test]
+    fn labels_are_reserved() {
+        let mut instructions = generate_builtins();
+        instructions.append(&mut generate_data());
+
+        for instr in instructions {
+            if let Instruction::Label(name) = instr {
+                assert!(
+                    RESERVED_NAMES.contains(&name.as_str()),
+                    "'{}' is not reserved",
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn registers_are_reserved() {
+        let registers = [
+            Register::RAX,
+            Register::RBX,
+            Register::RCX,
+            Register::RDX,
+            Register::RSP,
+            Register::RBP,
+            Register::RSI,
+            Register::RDI,
+        ];
+
+        for register in registers.iter() {
+            for size in [1, 2, 4, 8].iter() {
+                assert!(is_assembly_keyword(register.sized(*size)));
+            }
+        }
+        for name in ["r8", "r12d", "r15w", "r9b", "qword", "rel"].iter() {
+            assert!(is_assembly_keyword(name));
+        }
+        assert!(!is_assembly_keyword("r16"));
+        assert!(!is_assembly_keyword("result"));
+    }
+}
@@ -19,3 +19,61 @@ pub fn generate(instr: &mut Vec<Instruction>) {
         ],
     );
 }
+
+// Calling print_string, prints out the entire String whose Pointer
+// and Length were pushed
+pub fn generate_string(instr: &mut Vec<Instruction>) {
+    helper::help_func(
+        "print_string",
+        instr,
+        vec![
+            Instruction::Move(Register::RAX.to_string(), "1".to_owned()),
+            Instruction::Move(Register::RDI.to_string(), "1".to_owned()),
+            Instruction::Move(Register::RSI.to_string(), "[rbp + 16]".to_owned()),
+            Instruction::Move(Register::RDX.to_string(), "[rbp + 24]".to_owned()),
+            Instruction::Syscall,
+        ],
+    );
+}
+
+// Calling print_int, prints out the pushed Value as a signed Decimal-Number.
+// The Digits are written backwards into a Buffer on the Stack, which is
+// then written out with a single Syscall
+pub fn generate_int(instr: &mut Vec<Instruction>) {
+    helper::help_func(
+        "print_int",
+        instr,
+        vec![
+            Instruction::Sub(Register::RSP.to_string(), "32".to_owned()),
+            Instruction::Move(Register::RAX.to_string(), "[rbp + 16]".to_owned()),
+            Instruction::Lea(Register::RSI.to_string(), "[rbp - 1]".to_owned()),
+            Instruction::Move(Register::RCX.to_string(), "10".to_owned()),
+            Instruction::Label("print_int_digit".to_owned()),
+            Instruction::Cqo,
+            Instruction::Idiv(Register::RCX.to_string()),
+            // The Remainder of a negative Value is negative as well, which
+            // also works for the smallest Value that can't be negated
+            Instruction::Cmp(Register::RDX.to_string(), "0".to_owned()),
+            Instruction::Jge("print_int_positive".to_owned()),
+            Instruction::Neg(Register::RDX.to_string()),
+            Instruction::Label("print_int_positive".to_owned()),
+            Instruction::Add(Register::RDX.to_string(), "48".to_owned()),
+            Instruction::Move("[rsi]".to_owned(), "dl".to_owned()),
+            Instruction::Sub(Register::RSI.to_string(), "1".to_owned()),
+            Instruction::Cmp(Register::RAX.to_string(), "0".to_owned()),
+            Instruction::Jne("print_int_digit".to_owned()),
+            Instruction::Cmp("qword [rbp + 16]".to_owned(), "0".to_owned()),
+            Instruction::Jge("print_int_write".to_owned()),
+            Instruction::Move("byte [rsi]".to_owned(), "45".to_owned()),
+            Instruction::Sub(Register::RSI.to_string(), "1".to_owned()),
+            Instruction::Label("print_int_write".to_owned()),
+            Instruction::Add(Register::RSI.to_string(), "1".to_owned()),
+            Instruction::Move(Register::RDX.to_string(), Register::RBP.to_string()),
+            Instruction::Sub(Register::RDX.to_string(), Register::RSI.to_string()),
+            Instruction::Move(Register::RAX.to_string(), "1".to_owned()),
+            Instruction::Move(Register::RDI.to_string(), "1".to_owned()),
+            Instruction::Syscall,
+            Instruction::Move(Register::RSP.to_string(), Register::RBP.to_string()),
+        ],
+    );
+}
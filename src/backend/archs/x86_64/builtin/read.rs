@@ -0,0 +1,190 @@
+use crate::backend::archs::x86_64::{
+    asm::{Instruction, Register},
+    builtin::helper,
+};
+
+/// The Number of Bytes that are read from stdin at once
+const BUFFER_SIZE: usize = 4096;
+
+// The Input is read into a Buffer, where 'read_position' is the Index of the
+// next unread Byte and 'read_length' the Number of valid Bytes in it
+pub fn generate_data() -> Vec<Instruction> {
+    vec![
+        Instruction::Label("read_buffer".to_owned()),
+        Instruction::Reserve(BUFFER_SIZE),
+        Instruction::Label("read_position".to_owned()),
+        Instruction::Reserve(8),
+        Instruction::Label("read_length".to_owned()),
+        Instruction::Reserve(8),
+        Instruction::Label("read_eof".to_owned()),
+        Instruction::Reserve(8),
+    ]
+}
+
+// Calling read_char, returns the next Byte of the Input or -1 at its End,
+// refilling the Buffer with a single Syscall once it was used up
+pub fn generate_char(instr: &mut Vec<Instruction>) {
+    helper::help_func(
+        "read_char",
+        instr,
+        vec![
+            Instruction::Move(Register::RAX.to_string(), "[rel read_position]".to_owned()),
+            Instruction::Cmp(Register::RAX.to_string(), "[rel read_length]".to_owned()),
+            Instruction::Jl("read_char_buffered".to_owned()),
+            Instruction::Move(Register::RAX.to_string(), "0".to_owned()),
+            Instruction::Move(Register::RDI.to_string(), "0".to_owned()),
+            Instruction::Lea(Register::RSI.to_string(), "[rel read_buffer]".to_owned()),
+            Instruction::Move(Register::RDX.to_string(), BUFFER_SIZE.to_string()),
+            Instruction::Syscall,
+            // Errors are treated the same as the End of the Input
+            Instruction::Cmp(Register::RAX.to_string(), "0".to_owned()),
+            Instruction::Jg("read_char_filled".to_owned()),
+            Instruction::Move("qword [rel read_eof]".to_owned(), "1".to_owned()),
+            Instruction::Move(Register::RAX.to_string(), "-1".to_owned()),
+            Instruction::Jmp("read_char_end".to_owned()),
+            Instruction::Label("read_char_filled".to_owned()),
+            Instruction::Move("[rel read_length]".to_owned(), Register::RAX.to_string()),
+            Instruction::Move(Register::RAX.to_string(), "0".to_owned()),
+            Instruction::Label("read_char_buffered".to_owned()),
+            Instruction::Lea(Register::RSI.to_string(), "[rel read_buffer]".to_owned()),
+            Instruction::Movzx(Register::RDX.to_string(), "byte [rsi + rax]".to_owned()),
+            Instruction::Add(Register::RAX.to_string(), "1".to_owned()),
+            Instruction::Move("[rel read_position]".to_owned(), Register::RAX.to_string()),
+            Instruction::Move(Register::RAX.to_string(), Register::RDX.to_string()),
+            Instruction::Label("read_char_end".to_owned()),
+        ],
+    );
+}
+
+/// Jumps to the Target if the Character in 'rax' is not a Digit
+fn jump_unless_digit(target: &str) -> Vec<Instruction> {
+    vec![
+        Instruction::Cmp(Register::RAX.to_string(), "48".to_owned()),
+        Instruction::Jl(target.to_owned()),
+        Instruction::Cmp(Register::RAX.to_string(), "57".to_owned()),
+        Instruction::Jg(target.to_owned()),
+    ]
+}
+
+// Calling read_int, skips everything that is not part of a Number and then
+// returns the signed Decimal-Number that follows. The first Character after
+// the Number is put back into the Buffer. If the Input ends before any Digit
+// is found, 0 is returned and the End is reported by eof, but the End directly
+// after a Number is only reported by the next read
+pub fn generate_int(instr: &mut Vec<Instruction>) {
+    let mut content = vec![
+        // The Value is stored at [rbp - 8] and its Sign at [rbp - 16]
+        Instruction::Sub(Register::RSP.to_string(), "16".to_owned()),
+        Instruction::Move("qword [rbp - 8]".to_owned(), "0".to_owned()),
+        Instruction::Move("qword [rbp - 16]".to_owned(), "1".to_owned()),
+        Instruction::Label("read_int_skip".to_owned()),
+        Instruction::Call("read_char".to_owned()),
+        // Checks the Character in 'rax' without reading a new one
+        Instruction::Label("read_int_check".to_owned()),
+        Instruction::Cmp(Register::RAX.to_string(), "0".to_owned()),
+        Instruction::Jl("read_int_end".to_owned()),
+        Instruction::Move("qword [rbp - 16]".to_owned(), "1".to_owned()),
+        Instruction::Cmp(Register::RAX.to_string(), "45".to_owned()),
+        Instruction::Jne("read_int_first".to_owned()),
+        // A Minus only belongs to the Number if a Digit follows it,
+        // otherwise the next Character is checked again
+        Instruction::Move("qword [rbp - 16]".to_owned(), "-1".to_owned()),
+        Instruction::Call("read_char".to_owned()),
+    ];
+    content.append(&mut jump_unless_digit("read_int_check"));
+    content.push(Instruction::Jmp("read_int_digit".to_owned()));
+
+    // Whitespace and any other invalid Characters are skipped
+    content.push(Instruction::Label("read_int_first".to_owned()));
+    content.append(&mut jump_unless_digit("read_int_skip"));
+
+    content.append(&mut vec![
+        Instruction::Label("read_int_digit".to_owned()),
+        Instruction::Sub(Register::RAX.to_string(), "48".to_owned()),
+        Instruction::Move(Register::RCX.to_string(), Register::RAX.to_string()),
+        Instruction::Move(Register::RAX.to_string(), "[rbp - 8]".to_owned()),
+        Instruction::Imul(Register::RAX.to_string(), "10".to_owned()),
+        Instruction::Add(Register::RAX.to_string(), Register::RCX.to_string()),
+        Instruction::Move("[rbp - 8]".to_owned(), Register::RAX.to_string()),
+        Instruction::Call("read_char".to_owned()),
+    ]);
+    content.append(&mut jump_unless_digit("read_int_done"));
+    content.append(&mut vec![
+        Instruction::Jmp("read_int_digit".to_owned()),
+        Instruction::Label("read_int_done".to_owned()),
+        Instruction::Cmp(Register::RAX.to_string(), "0".to_owned()),
+        Instruction::Jl("read_int_last".to_owned()),
+        Instruction::Sub("qword [rel read_position]".to_owned(), "1".to_owned()),
+        Instruction::Jmp("read_int_end".to_owned()),
+        // The Number is still valid, so the End is not reported yet
+        Instruction::Label("read_int_last".to_owned()),
+        Instruction::Move("qword [rel read_eof]".to_owned(), "0".to_owned()),
+        Instruction::Label("read_int_end".to_owned()),
+        Instruction::Move(Register::RAX.to_string(), "[rbp - 8]".to_owned()),
+        Instruction::Imul(Register::RAX.to_string(), "[rbp - 16]".to_owned()),
+        Instruction::Move(Register::RSP.to_string(), Register::RBP.to_string()),
+    ]);
+
+    helper::help_func("read_int", instr, content);
+}
+
+// Calling eof, returns whether a read already reached the End of the Input
+pub fn generate_eof(instr: &mut Vec<Instruction>) {
+    helper::help_func(
+        "eof",
+        instr,
+        vec![Instruction::Move(
+            Register::RAX.to_string(),
+            "[rel read_eof]".to_owned(),
+        )],
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The Instructions of read_int that directly follow the Label
+    fn after_label(label: &str, count: usize) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        generate_int(&mut instructions);
+
+        let position = instructions
+            .iter()
+            .position(|instr| instr == &Instruction::Label(label.to_owned()))
+            .unwrap();
+        instructions
+            .drain(position + 1..position + 1 + count)
+            .collect()
+    }
+
+    #[test]
+    fn invalid_characters_are_skipped() {
+        // Anything that does not start a Number is consumed by reading the next one
+        assert_eq!(
+            jump_unless_digit("read_int_skip"),
+            after_label("read_int_first", 4)
+        );
+    }
+
+    #[test]
+    fn end_after_number() {
+        // A Number that ends with the Input is returned without reporting the End
+        assert_eq!(
+            vec![Instruction::Move(
+                "qword [rel read_eof]".to_owned(),
+                "0".to_owned()
+            )],
+            after_label("read_int_last", 1)
+        );
+
+        // Without any Digits the End is reported, as it was set by read_char
+        assert_eq!(
+            vec![
+                Instruction::Cmp(Register::RAX.to_string(), "0".to_owned()),
+                Instruction::Jl("read_int_end".to_owned()),
+            ],
+            after_label("read_int_check", 2)
+        );
+    }
+}
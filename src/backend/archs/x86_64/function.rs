@@ -1,7 +1,9 @@
+use std::{cell::RefCell, collections::HashMap};
+
 use rand::{thread_rng, Rng};
 
-use crate::backend::VariableOffsets;
-use crate::frontend::ir::{IRFunction, IRNode};
+use crate::error::CompileError;
+use crate::frontend::ir::{IRFunction, IRNode, IRType};
 
 use super::asm::{Instruction, Register};
 
@@ -9,6 +11,64 @@ mod conditionals;
 mod expression;
 mod variables;
 
+use variables::Scope;
+
+/// Everything about the entire Program that is needed while
+/// generating a single Function
+#[derive(Debug, Default, PartialEq)]
+pub struct Program {
+    /// The Return-Type of every Function
+    pub return_types: HashMap<String, Option<IRType>>,
+    /// All the String-Literals, which are placed in the Data-Section
+    pub strings: RefCell<Vec<String>>,
+}
+
+impl Program {
+    pub fn new(ir: &HashMap<String, IRFunction>) -> Self {
+        Self {
+            return_types: ir
+                .iter()
+                .map(|(name, func)| (name.clone(), func.return_type.clone()))
+                .collect(),
+            strings: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// The Label of the String-Literal in the Data-Section, where
+    /// the same Literal is only stored once
+    pub fn string_label(&self, value: &str) -> String {
+        let mut strings = self.strings.borrow_mut();
+        let index = match strings.iter().position(|s| s == value) {
+            Some(index) => index,
+            None => {
+                strings.push(value.to_owned());
+                strings.len() - 1
+            }
+        };
+
+        string_label(index)
+    }
+
+    /// The Labels and Bytes of all the String-Literals
+    pub fn generate_data(&self) -> Vec<Instruction> {
+        let mut result = Vec::new();
+
+        for (index, value) in self.strings.borrow().iter().enumerate() {
+            result.push(Instruction::Label(string_label(index)));
+            // An empty String only needs the Label
+            if !value.is_empty() {
+                result.push(Instruction::Bytes(value.as_bytes().to_vec()));
+            }
+        }
+
+        result
+    }
+}
+
+fn string_label(index: usize) -> String {
+    format!("STR_{}", index)
+}
+
 fn generate_random_string(length: usize) -> String {
     const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
 
@@ -18,11 +78,41 @@ fn generate_random_string(length: usize) -> String {
         .collect()
 }
 
+/// Generates the Statements of a nested Block, whose Variables are
+/// only visible inside of it and whose Stack-Slots are reused afterwards
+fn generate_block<F>(
+    statements: &[Vec<IRNode>],
+    outer: &Scope,
+    max_offset: &mut i64,
+    return_type: Option<&IRType>,
+    pre_return: &F,
+) -> Result<Vec<Instruction>, CompileError>
+where
+    F: Fn(&mut Vec<Instruction>),
+{
+    let mut result = Vec::new();
+
+    let mut scope = outer.clone();
+    for statement in statements.iter() {
+        result.append(&mut generate_statement(
+            statement,
+            &mut scope,
+            max_offset,
+            return_type,
+            pre_return,
+        )?);
+    }
+
+    Ok(result)
+}
+
 fn generate_statement<F>(
     statement: &[IRNode],
-    variables: &VariableOffsets,
+    scope: &mut Scope,
+    max_offset: &mut i64,
+    return_type: Option<&IRType>,
     pre_return: &F,
-) -> Vec<Instruction>
+) -> Result<Vec<Instruction>, CompileError>
 where
     F: Fn(&mut Vec<Instruction>),
 {
@@ -30,42 +120,78 @@ where
 
     for step in statement.iter() {
         match step {
-            &IRNode::Assignment(ref var_name, ref exp) => {
+            IRNode::Assignment(ref var_name, ref exp, ref span) => {
                 result.push(Instruction::Comment("Assignment".to_string()));
-                let target_offset = match variables.get(var_name) {
+                let target = match scope.vars.get(var_name) {
                     Some(v) => v,
                     None => {
-                        panic!("Cant find variable: {:?}", var_name);
+                        return Err(CompileError::codegen(
+                            format!("Cant find variable: {:?}", var_name),
+                            *span,
+                        ));
                     }
                 };
-                let target = format!("[rbp - {}]", target_offset);
 
-                result.append(&mut expression::generate(exp, variables));
+                result.append(&mut expression::generate(exp, scope)?);
+
+                result.append(&mut variables::store(target, Register::RAX));
+            }
+            IRNode::DeclareVariable(ref name, ref var_type, ref init, _) => {
+                // The initial Value can still refer to a shadowed Variable
+                if let Some(exp) = init {
+                    result.push(Instruction::Comment("Declaration".to_string()));
+                    result.append(&mut expression::generate(exp, scope)?);
+                }
+
+                let offset = scope.declare(name, var_type);
+                *max_offset = (*max_offset).max(offset);
 
-                result.push(Instruction::Move(target, Register::RAX.to_string()));
+                if init.is_some() {
+                    result.append(&mut variables::store(&scope.vars[name], Register::RAX));
+                }
             }
-            &IRNode::DeclareVariable(_, _) => {}
-            &IRNode::Conditional(ref cond, ref nodes) => {
+            IRNode::Conditional(ref cond, ref nodes, ref else_nodes, _) => {
                 result.push(Instruction::Comment("Conditional".to_string()));
-                let end_target = format!("{}_END", generate_random_string(10));
+                let random_id = generate_random_string(10);
+                let else_target = format!("{}_ELSE", random_id);
+                let end_target = format!("{}_END", random_id);
 
+                // Skip the then-Block if the condition is FALSE
+                let skip_target = match else_nodes {
+                    Some(_) => else_target.clone(),
+                    None => end_target.clone(),
+                };
                 result.append(&mut conditionals::generate_inverse_jump(
                     cond,
-                    end_target.clone(),
-                    variables,
-                ));
+                    skip_target,
+                    scope,
+                )?);
+
+                result.append(&mut generate_block(
+                    nodes,
+                    scope,
+                    max_offset,
+                    return_type,
+                    pre_return,
+                )?);
+
+                if let Some(else_nodes) = else_nodes {
+                    // The end of the then-Block jumps over the else-Block
+                    result.push(Instruction::Jmp(end_target.clone()));
+                    result.push(Instruction::Label(else_target));
 
-                for cond_statements in nodes.iter() {
-                    result.append(&mut generate_statement(
-                        &cond_statements,
-                        variables,
+                    result.append(&mut generate_block(
+                        else_nodes,
+                        scope,
+                        max_offset,
+                        return_type,
                         pre_return,
-                    ));
+                    )?);
                 }
 
                 result.push(Instruction::Label(end_target));
             }
-            &IRNode::Loop(ref cond, ref nodes) => {
+            IRNode::Loop(ref cond, ref nodes, _) => {
                 result.push(Instruction::Comment("Loop".to_string()));
                 let random_id = generate_random_string(10);
                 let top_target = format!("{}_TOP", random_id);
@@ -75,54 +201,105 @@ where
                 result.append(&mut conditionals::generate_inverse_jump(
                     cond,
                     end_target.clone(),
-                    variables,
-                ));
+                    scope,
+                )?);
 
                 // The start of the loop
                 result.push(Instruction::Label(top_target.clone()));
 
                 // Generate all the actual loop code
-                for cond_statements in nodes.iter() {
-                    result.append(&mut generate_statement(
-                        &cond_statements,
-                        variables,
-                        pre_return,
-                    ));
-                }
+                result.append(&mut generate_block(
+                    nodes,
+                    scope,
+                    max_offset,
+                    return_type,
+                    pre_return,
+                )?);
 
                 // Generate the Comparison and jump to top to actually loop
-                result.append(&mut conditionals::generate_jump(
-                    cond, top_target, variables,
-                ));
+                result.append(&mut conditionals::generate_jump(cond, top_target, scope)?);
 
                 // The end of the Loop
                 // if this is reached the loop is done
                 result.push(Instruction::Label(end_target));
             }
-            IRNode::Return(ref raw_exp) => {
+            IRNode::Return(ref raw_exp, ref span) => {
                 result.push(Instruction::Comment("Return".to_string()));
-                if let Some(exp) = raw_exp {
-                    result.append(&mut expression::generate(exp, variables));
-                }
+                match (raw_exp, return_type) {
+                    (Some(exp), Some(ret_type)) => {
+                        // All the Integers can be returned as any other Integer-Type
+                        match expression::value_type(exp, scope) {
+                            Some(found)
+                                if found != *ret_type
+                                    && !(found.is_integer() && ret_type.is_integer()) =>
+                            {
+                                return Err(CompileError::codegen(
+                                    format!(
+                                        "Expected a return value of type {}, found {}",
+                                        ret_type, found
+                                    ),
+                                    *span,
+                                ));
+                            }
+                            _ => {}
+                        };
+
+                        result.append(&mut expression::generate(exp, scope)?);
+                        // The Caller expects the Value to already fit the Return-Type
+                        result.append(&mut variables::narrow(ret_type, Register::RAX));
+                    }
+                    (None, None) => {}
+                    (Some(_), None) => {
+                        return Err(CompileError::codegen(
+                            "Cant return a value from a function without a return type",
+                            *span,
+                        ));
+                    }
+                    (None, Some(ret_type)) => {
+                        return Err(CompileError::codegen(
+                            format!("Expected a return value of type {}", ret_type),
+                            *span,
+                        ));
+                    }
+                };
 
                 pre_return(&mut result);
                 result.push(Instruction::Return);
             }
-            IRNode::SingleExpression(ref exp) => {
+            IRNode::SingleExpression(ref exp, _) => {
                 result.push(Instruction::Comment("Single-Expression".to_string()));
-                result.append(&mut expression::generate(exp, variables));
+                result.append(&mut expression::generate(exp, scope)?);
             }
         };
     }
 
-    result
+    Ok(result)
 }
 
-pub fn generate_function(func: &IRFunction) -> Vec<Instruction> {
-    let mut final_asm = Vec::new();
+pub fn generate_function(
+    func: &IRFunction,
+    program: &Program,
+) -> Result<Vec<Instruction>, CompileError> {
+    let pre_return = |instr: &mut Vec<Instruction>| {
+        instr.push(Instruction::Move(
+            Register::RSP.to_string(),
+            Register::RBP.to_string(),
+        ));
+        instr.push(Instruction::Pop(Register::RBP.to_string()));
+    };
 
-    let (vars, var_offset) = variables::generate_offsets(func);
+    // Actual code, which also determines how much Stack-Space
+    // the Variables need
+    let mut max_offset = 0;
+    let mut body = generate_block(
+        &func.statements,
+        &Scope::new(func, program),
+        &mut max_offset,
+        func.return_type.as_ref(),
+        &pre_return,
+    )?;
 
+    let mut final_asm = Vec::new();
     final_asm.push(Instruction::Label(func.name.clone()));
     final_asm.push(Instruction::Push(Register::RBP.to_string()));
     final_asm.push(Instruction::Move(
@@ -131,24 +308,120 @@ pub fn generate_function(func: &IRFunction) -> Vec<Instruction> {
     ));
     final_asm.push(Instruction::Sub(
         Register::RSP.to_string(),
-        format!("{}", var_offset),
+        format!("{}", variables::frame_size(max_offset)),
     ));
 
-    let pre_return = |instr: &mut Vec<Instruction>| {
-        instr.push(Instruction::Move(
-            Register::RSP.to_string(),
-            Register::RBP.to_string(),
-        ));
-        instr.push(Instruction::Pop(Register::RBP.to_string()));
+    final_asm.append(&mut body);
+
+    pre_return(&mut final_asm);
+    final_asm.push(Instruction::Return);
+
+    Ok(final_asm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::frontend::{
+        ir::{IRExpression, IRValue},
+        Span,
     };
 
-    // Actual code
-    for statement in func.statements.iter() {
-        final_asm.append(&mut generate_statement(statement, &vars, &pre_return));
+    #[test]
+    fn return_type_mismatch() {
+        let with_value = vec![IRNode::Return(
+            Some(IRExpression::Variable("x".to_owned(), Span::default())),
+            Span::default(),
+        )];
+        let without_value = vec![IRNode::Return(None, Span::default())];
+        let program = Program::default();
+        let mut scope = Scope::empty(&program);
+
+        assert_eq!(
+            Err(CompileError::codegen(
+                "Cant return a value from a function without a return type",
+                Span::default()
+            )),
+            generate_statement(&with_value, &mut scope, &mut 0, None, &|_| {})
+        );
+        assert_eq!(
+            Err(CompileError::codegen(
+                "Expected a return value of type i64",
+                Span::default()
+            )),
+            generate_statement(
+                &without_value,
+                &mut scope,
+                &mut 0,
+                Some(&IRType::I64),
+                &|_| {}
+            )
+        );
+
+        let bool_value = vec![IRNode::Return(
+            Some(IRExpression::Value(IRValue::Bool(true), Span::default())),
+            Span::default(),
+        )];
+        assert_eq!(
+            Err(CompileError::codegen(
+                "Expected a return value of type i64, found bool",
+                Span::default()
+            )),
+            generate_statement(&bool_value, &mut scope, &mut 0, Some(&IRType::I64), &|_| {})
+        );
     }
 
-    pre_return(&mut final_asm);
-    final_asm.push(Instruction::Return);
+    #[test]
+    fn narrowed_return() {
+        let content = "func addu(a: u8, b: u8) -> u8 {
+  return a + b;
+}
+";
+        let ir = crate::frontend::parse(content.to_owned()).unwrap();
+        let asm = generate_function(&ir["addu"], &Program::new(&ir)).unwrap();
 
-    final_asm
+        let position = asm
+            .iter()
+            .position(|instr| instr == &Instruction::Return)
+            .unwrap();
+        assert!(asm[..position].contains(&Instruction::Movzx("rax".to_owned(), "al".to_owned())));
+    }
+
+    #[test]
+    fn nested_scopes() {
+        let content = "func main() {
+  number x = 1;
+  if (x > 0) {
+    number y = 2;
+    u8 x = 3;
+  }
+  while (x < 5) {
+    number z = x;
+    x = z + 1;
+  }
+}
+";
+        let ir = crate::frontend::parse(content.to_owned()).unwrap();
+        let asm = generate_function(&ir["main"], &Program::new(&ir)).unwrap();
+
+        // The Slots of the if-Block are reused by the Loop
+        assert_eq!(Instruction::Sub("rsp".to_owned(), "32".to_owned()), asm[3]);
+        let stores: Vec<&Instruction> = asm
+            .iter()
+            .filter(
+                |instr| matches!(instr, Instruction::Move(target, _) if target.starts_with("[rbp")),
+            )
+            .collect();
+        assert_eq!(
+            vec![
+                &Instruction::Move("[rbp - 8]".to_owned(), "rax".to_owned()),
+                &Instruction::Move("[rbp - 16]".to_owned(), "rax".to_owned()),
+                &Instruction::Move("[rbp - 17]".to_owned(), "al".to_owned()),
+                &Instruction::Move("[rbp - 16]".to_owned(), "rax".to_owned()),
+                &Instruction::Move("[rbp - 8]".to_owned(), "rax".to_owned()),
+            ],
+            stores
+        );
+    }
 }
@@ -1,91 +1,261 @@
 use crate::{
-    backend::{
-        archs::x86_64::asm::{Instruction, Register},
-        VariableOffsets,
-    },
-    frontend::ir::IRComparison,
+    backend::archs::x86_64::asm::{Instruction, Register},
+    error::CompileError,
+    frontend::ir::{IRComparison, IRCondition},
 };
 
-use super::expression;
+use super::{expression, generate_random_string, variables::Scope};
+
+/// Checks if the Comparison has to be done unsigned, which is the
+/// case as soon as one of the Sides is a 'u64'
+fn is_unsigned(cond: &IRComparison, scope: &Scope) -> bool {
+    let (left, right) = cond.operands();
+    expression::is_u64(left, scope) || expression::is_u64(right, scope)
+}
+
+/// Generates the instructions to compare the two Sides of the
+/// Comparison, which sets the Flags for the following Jump
+fn generate_compare(cond: &IRComparison, scope: &Scope) -> Result<Vec<Instruction>, CompileError> {
+    let mut result = Vec::new();
+
+    let (left, right) = cond.operands();
+    result.append(&mut expression::generate(left, scope)?);
+    result.push(Instruction::Push(Register::RAX.to_string()));
+    result.append(&mut expression::generate(right, scope)?);
+    result.push(Instruction::Pop(Register::RBX.to_string()));
+
+    result.push(Instruction::Cmp(
+        Register::RBX.to_string(),
+        Register::RAX.to_string(),
+    ));
+
+    Ok(result)
+}
 
 /// Generates the instructions to jump to the Target
-/// when the condition is false / the opposite
-pub fn generate_inverse_jump(
+/// when the Comparison is false
+fn generate_inverse_comparison_jump(
     cond: &IRComparison,
     jump_target: String,
-    variables: &VariableOffsets,
-) -> Vec<Instruction> {
+    scope: &Scope,
+) -> Result<Vec<Instruction>, CompileError> {
+    let mut result = generate_compare(cond, scope)?;
+
+    let jump_instr = match (cond, is_unsigned(cond, scope)) {
+        (IRComparison::Equals(_, _), _) => Instruction::Jne(jump_target),
+        (IRComparison::NotEquals(_, _), _) => Instruction::Je(jump_target),
+        (IRComparison::LessThan(_, _), false) => Instruction::Jge(jump_target),
+        (IRComparison::LessEqual(_, _), false) => Instruction::Jg(jump_target),
+        (IRComparison::GreaterThan(_, _), false) => Instruction::Jle(jump_target),
+        (IRComparison::GreaterEqual(_, _), false) => Instruction::Jl(jump_target),
+        (IRComparison::LessThan(_, _), true) => Instruction::Jae(jump_target),
+        (IRComparison::LessEqual(_, _), true) => Instruction::Ja(jump_target),
+        (IRComparison::GreaterThan(_, _), true) => Instruction::Jbe(jump_target),
+        (IRComparison::GreaterEqual(_, _), true) => Instruction::Jb(jump_target),
+    };
+    result.push(jump_instr);
+
+    Ok(result)
+}
+
+/// Generates the instructions to jump to the Target
+/// when the Comparison is true
+fn generate_comparison_jump(
+    cond: &IRComparison,
+    jump_target: String,
+    scope: &Scope,
+) -> Result<Vec<Instruction>, CompileError> {
+    let mut result = generate_compare(cond, scope)?;
+
+    let jump_instr = match (cond, is_unsigned(cond, scope)) {
+        (IRComparison::Equals(_, _), _) => Instruction::Je(jump_target),
+        (IRComparison::NotEquals(_, _), _) => Instruction::Jne(jump_target),
+        (IRComparison::LessThan(_, _), false) => Instruction::Jl(jump_target),
+        (IRComparison::LessEqual(_, _), false) => Instruction::Jle(jump_target),
+        (IRComparison::GreaterThan(_, _), false) => Instruction::Jg(jump_target),
+        (IRComparison::GreaterEqual(_, _), false) => Instruction::Jge(jump_target),
+        (IRComparison::LessThan(_, _), true) => Instruction::Jb(jump_target),
+        (IRComparison::LessEqual(_, _), true) => Instruction::Jbe(jump_target),
+        (IRComparison::GreaterThan(_, _), true) => Instruction::Ja(jump_target),
+        (IRComparison::GreaterEqual(_, _), true) => Instruction::Jae(jump_target),
+    };
+    result.push(jump_instr);
+
+    Ok(result)
+}
+
+/// Generates the instructions to jump to the Target
+/// when the condition is false / the opposite.
+///
+/// The Conditions are short-circuited, so the right Side of an
+/// '&&' or '||' is only evaluated if the left Side does not
+/// already decide the Result
+pub fn generate_inverse_jump(
+    cond: &IRCondition,
+    jump_target: String,
+    scope: &Scope,
+) -> Result<Vec<Instruction>, CompileError> {
     let mut result = Vec::new();
 
     match cond {
-        IRComparison::Equals(left, right) | IRComparison::GreaterThan(left, right) => {
-            result.append(&mut expression::generate(&left, variables));
-            result.push(Instruction::Push(Register::RAX.to_string()));
-            result.append(&mut expression::generate(&right, variables));
-            result.push(Instruction::Pop(Register::RBX.to_string()));
-
-            result.push(Instruction::Cmp(
-                Register::RBX.to_string(),
-                Register::RAX.to_string(),
-            ));
+        IRCondition::Comparison(ref comp) => {
+            result.append(&mut generate_inverse_comparison_jump(
+                comp,
+                jump_target,
+                scope,
+            )?);
+        }
+        IRCondition::Expression(ref exp) => {
+            result.append(&mut expression::generate(exp, scope)?);
+            result.push(Instruction::Cmp(Register::RAX.to_string(), "0".to_string()));
+            result.push(Instruction::Je(jump_target));
+        }
+        IRCondition::And(ref left, ref right) => {
+            result.append(&mut generate_inverse_jump(
+                left,
+                jump_target.clone(),
+                scope,
+            )?);
+            result.append(&mut generate_inverse_jump(right, jump_target, scope)?);
+        }
+        IRCondition::Or(ref left, ref right) => {
+            // If the left Side is true, the entire Condition is true
+            let skip_target = format!("{}_OR", generate_random_string(10));
 
-            let jump_instr = match cond {
-                IRComparison::Equals(_, _) => Instruction::Jne(jump_target),
-                IRComparison::GreaterThan(_, _) => Instruction::Jle(jump_target),
-            };
-            result.push(jump_instr);
+            result.append(&mut generate_jump(left, skip_target.clone(), scope)?);
+            result.append(&mut generate_inverse_jump(right, jump_target, scope)?);
+            result.push(Instruction::Label(skip_target));
+        }
+        IRCondition::Not(ref inner) => {
+            result.append(&mut generate_jump(inner, jump_target, scope)?);
         }
     };
 
-    result
+    Ok(result)
 }
 
 /// Generates the instructions to jump to the Target
-/// when the condition is false / the opposite
+/// when the condition is true, using the same short-circuiting
+/// as `generate_inverse_jump`
 pub fn generate_jump(
-    cond: &IRComparison,
+    cond: &IRCondition,
     jump_target: String,
-    variables: &VariableOffsets,
-) -> Vec<Instruction> {
+    scope: &Scope,
+) -> Result<Vec<Instruction>, CompileError> {
     let mut result = Vec::new();
 
     match cond {
-        IRComparison::Equals(left, right) | IRComparison::GreaterThan(left, right) => {
-            result.append(&mut expression::generate(&left, variables));
-            result.push(Instruction::Push(Register::RAX.to_string()));
-            result.append(&mut expression::generate(&right, variables));
-            result.push(Instruction::Pop(Register::RBX.to_string()));
-
-            result.push(Instruction::Cmp(
-                Register::RBX.to_string(),
+        IRCondition::Comparison(ref comp) => {
+            result.append(&mut generate_comparison_jump(comp, jump_target, scope)?);
+        }
+        IRCondition::Expression(ref exp) => {
+            result.append(&mut expression::generate(exp, scope)?);
+            result.push(Instruction::Cmp(Register::RAX.to_string(), "0".to_string()));
+            result.push(Instruction::Jne(jump_target));
+        }
+        IRCondition::And(ref left, ref right) => {
+            // If the left Side is false, the entire Condition is false
+            let skip_target = format!("{}_AND", generate_random_string(10));
+
+            result.append(&mut generate_inverse_jump(
+                left,
+                skip_target.clone(),
+                scope,
+            )?);
+            result.append(&mut generate_jump(right, jump_target, scope)?);
+            result.push(Instruction::Label(skip_target));
+        }
+        IRCondition::Or(ref left, ref right) => {
+            result.append(&mut generate_jump(left, jump_target.clone(), scope)?);
+            result.append(&mut generate_jump(right, jump_target, scope)?);
+        }
+        IRCondition::Not(ref inner) => {
+            result.append(&mut generate_inverse_jump(inner, jump_target, scope)?);
+        }
+    };
+
+    Ok(result)
+}
+
+/// Generates the instructions to evaluate the Condition as a
+/// boolean Value, which is placed in 'rax' as either 0 or 1
+pub fn generate_value(cond: &IRCondition, scope: &Scope) -> Result<Vec<Instruction>, CompileError> {
+    let mut result = Vec::new();
+
+    match cond {
+        IRCondition::Comparison(ref comp) => {
+            result.append(&mut generate_compare(comp, scope)?);
+
+            let target = Register::RAX.sized(1).to_string();
+            let set_instr = match (comp, is_unsigned(comp, scope)) {
+                (IRComparison::Equals(_, _), _) => Instruction::Sete(target),
+                (IRComparison::NotEquals(_, _), _) => Instruction::Setne(target),
+                (IRComparison::LessThan(_, _), false) => Instruction::Setl(target),
+                (IRComparison::LessEqual(_, _), false) => Instruction::Setle(target),
+                (IRComparison::GreaterThan(_, _), false) => Instruction::Setg(target),
+                (IRComparison::GreaterEqual(_, _), false) => Instruction::Setge(target),
+                (IRComparison::LessThan(_, _), true) => Instruction::Setb(target),
+                (IRComparison::LessEqual(_, _), true) => Instruction::Setbe(target),
+                (IRComparison::GreaterThan(_, _), true) => Instruction::Seta(target),
+                (IRComparison::GreaterEqual(_, _), true) => Instruction::Setae(target),
+            };
+            result.push(set_instr);
+            result.push(Instruction::Movzx(
                 Register::RAX.to_string(),
+                Register::RAX.sized(1).to_string(),
             ));
+        }
+        IRCondition::Expression(ref exp) => {
+            result.append(&mut expression::generate(exp, scope)?);
+        }
+        _ => {
+            // The logical Operators are short-circuited using Jumps
+            let random_id = generate_random_string(10);
+            let false_target = format!("{}_FALSE", random_id);
+            let end_target = format!("{}_END", random_id);
 
-            let jump_instr = match cond {
-                IRComparison::Equals(_, _) => Instruction::Je(jump_target),
-                IRComparison::GreaterThan(_, _) => Instruction::Jg(jump_target),
-            };
-            result.push(jump_instr);
+            result.append(&mut generate_inverse_jump(
+                cond,
+                false_target.clone(),
+                scope,
+            )?);
+            result.push(Instruction::Move(
+                Register::RAX.to_string(),
+                "1".to_string(),
+            ));
+            result.push(Instruction::Jmp(end_target.clone()));
+            result.push(Instruction::Label(false_target));
+            result.push(Instruction::Move(
+                Register::RAX.to_string(),
+                "0".to_string(),
+            ));
+            result.push(Instruction::Label(end_target));
         }
     };
 
-    result
+    Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::frontend::ir::{IRExpression, IRValue};
+    use crate::frontend::{
+        ir::{IRExpression, IRType, IRValue},
+        Span,
+    };
 
     use super::*;
 
+    use crate::backend::archs::x86_64::function::Program;
+
     #[test]
     fn simple_inverse_equal() {
-        let comparison = IRComparison::Equals(
-            IRExpression::Value(IRValue::Number(1)),
-            IRExpression::Value(IRValue::Number(2)),
-        );
+        let comparison = IRCondition::Comparison(IRComparison::Equals(
+            IRExpression::Value(IRValue::Number(1), Span::default()),
+            IRExpression::Value(IRValue::Number(2), Span::default()),
+        ));
         let target = "test_target".to_string();
-        let variables = VariableOffsets::new();
+        let program = Program::default();
+        let scope = Scope::empty(&program);
 
         let expected = vec![
             Instruction::Move(Register::RAX.to_string(), "1".to_string()),
@@ -97,19 +267,20 @@ mod tests {
         ];
 
         assert_eq!(
-            expected,
-            generate_inverse_jump(&comparison, target, &variables)
+            Ok(expected),
+            generate_inverse_jump(&comparison, target, &scope)
         );
     }
 
     #[test]
     fn simple_inverse_greater_than() {
-        let comparison = IRComparison::GreaterThan(
-            IRExpression::Value(IRValue::Number(1)),
-            IRExpression::Value(IRValue::Number(2)),
-        );
+        let comparison = IRCondition::Comparison(IRComparison::GreaterThan(
+            IRExpression::Value(IRValue::Number(1), Span::default()),
+            IRExpression::Value(IRValue::Number(2), Span::default()),
+        ));
         let target = "test_target".to_string();
-        let variables = VariableOffsets::new();
+        let program = Program::default();
+        let scope = Scope::empty(&program);
 
         let expected = vec![
             Instruction::Move(Register::RAX.to_string(), "1".to_string()),
@@ -121,19 +292,20 @@ mod tests {
         ];
 
         assert_eq!(
-            expected,
-            generate_inverse_jump(&comparison, target, &variables)
+            Ok(expected),
+            generate_inverse_jump(&comparison, target, &scope)
         );
     }
 
     #[test]
     fn simple_equal() {
-        let comparison = IRComparison::Equals(
-            IRExpression::Value(IRValue::Number(1)),
-            IRExpression::Value(IRValue::Number(2)),
-        );
+        let comparison = IRCondition::Comparison(IRComparison::Equals(
+            IRExpression::Value(IRValue::Number(1), Span::default()),
+            IRExpression::Value(IRValue::Number(2), Span::default()),
+        ));
         let target = "test_target".to_string();
-        let variables = VariableOffsets::new();
+        let program = Program::default();
+        let scope = Scope::empty(&program);
 
         let expected = vec![
             Instruction::Move(Register::RAX.to_string(), "1".to_string()),
@@ -144,27 +316,229 @@ mod tests {
             Instruction::Je(target.clone()),
         ];
 
-        assert_eq!(expected, generate_jump(&comparison, target, &variables));
+        assert_eq!(Ok(expected), generate_jump(&comparison, target, &scope));
     }
 
     #[test]
     fn simple_greater_than() {
-        let comparison = IRComparison::GreaterThan(
-            IRExpression::Value(IRValue::Number(1)),
-            IRExpression::Value(IRValue::Number(2)),
+        let comparison = IRCondition::Comparison(IRComparison::GreaterThan(
+            IRExpression::Value(IRValue::Number(1), Span::default()),
+            IRExpression::Value(IRValue::Number(2), Span::default()),
+        ));
+        let target = "test_target".to_string();
+        let program = Program::default();
+        let scope = Scope::empty(&program);
+
+        let expected = vec![
+            Instruction::Move(Register::RAX.to_string(), "1".to_string()),
+            Instruction::Push(Register::RAX.to_string()),
+            Instruction::Move(Register::RAX.to_string(), "2".to_string()),
+            Instruction::Pop(Register::RBX.to_string()),
+            Instruction::Cmp(Register::RBX.to_string(), Register::RAX.to_string()),
+            Instruction::Jg(target.clone()),
+        ];
+
+        assert_eq!(Ok(expected), generate_jump(&comparison, target, &scope));
+    }
+
+    #[test]
+    fn inverse_less_equal() {
+        let comparison = IRCondition::Comparison(IRComparison::LessEqual(
+            IRExpression::Value(IRValue::Number(1), Span::default()),
+            IRExpression::Value(IRValue::Number(2), Span::default()),
+        ));
+        let target = "test_target".to_string();
+        let program = Program::default();
+        let scope = Scope::empty(&program);
+
+        let expected = vec![
+            Instruction::Move(Register::RAX.to_string(), "1".to_string()),
+            Instruction::Push(Register::RAX.to_string()),
+            Instruction::Move(Register::RAX.to_string(), "2".to_string()),
+            Instruction::Pop(Register::RBX.to_string()),
+            Instruction::Cmp(Register::RBX.to_string(), Register::RAX.to_string()),
+            Instruction::Jg(target.clone()),
+        ];
+
+        assert_eq!(
+            Ok(expected),
+            generate_inverse_jump(&comparison, target, &scope)
+        );
+    }
+
+    #[test]
+    fn greater_equal() {
+        let comparison = IRCondition::Comparison(IRComparison::GreaterEqual(
+            IRExpression::Value(IRValue::Number(1), Span::default()),
+            IRExpression::Value(IRValue::Number(2), Span::default()),
+        ));
+        let target = "test_target".to_string();
+        let program = Program::default();
+        let scope = Scope::empty(&program);
+
+        let expected = vec![
+            Instruction::Move(Register::RAX.to_string(), "1".to_string()),
+            Instruction::Push(Register::RAX.to_string()),
+            Instruction::Move(Register::RAX.to_string(), "2".to_string()),
+            Instruction::Pop(Register::RBX.to_string()),
+            Instruction::Cmp(Register::RBX.to_string(), Register::RAX.to_string()),
+            Instruction::Jge(target.clone()),
+        ];
+
+        assert_eq!(Ok(expected), generate_jump(&comparison, target, &scope));
+    }
+
+    #[test]
+    fn inverse_and_short_circuits() {
+        let condition = IRCondition::And(
+            Box::new(IRCondition::Comparison(IRComparison::Equals(
+                IRExpression::Value(IRValue::Number(1), Span::default()),
+                IRExpression::Value(IRValue::Number(2), Span::default()),
+            ))),
+            Box::new(IRCondition::Comparison(IRComparison::LessThan(
+                IRExpression::Call("test".to_owned(), vec![], Span::default()),
+                IRExpression::Value(IRValue::Number(3), Span::default()),
+            ))),
+        );
+        let target = "test_target".to_string();
+        let program = Program::default();
+        let scope = Scope::empty(&program);
+
+        let expected = vec![
+            Instruction::Move(Register::RAX.to_string(), "1".to_string()),
+            Instruction::Push(Register::RAX.to_string()),
+            Instruction::Move(Register::RAX.to_string(), "2".to_string()),
+            Instruction::Pop(Register::RBX.to_string()),
+            Instruction::Cmp(Register::RBX.to_string(), Register::RAX.to_string()),
+            Instruction::Jne(target.clone()),
+            Instruction::Call("test".to_owned()),
+            Instruction::Push(Register::RAX.to_string()),
+            Instruction::Move(Register::RAX.to_string(), "3".to_string()),
+            Instruction::Pop(Register::RBX.to_string()),
+            Instruction::Cmp(Register::RBX.to_string(), Register::RAX.to_string()),
+            Instruction::Jge(target.clone()),
+        ];
+
+        assert_eq!(
+            Ok(expected),
+            generate_inverse_jump(&condition, target, &scope)
         );
+    }
+
+    #[test]
+    fn not_or() {
+        let condition = IRCondition::Not(Box::new(IRCondition::Or(
+            Box::new(IRCondition::Comparison(IRComparison::Equals(
+                IRExpression::Value(IRValue::Number(1), Span::default()),
+                IRExpression::Value(IRValue::Number(2), Span::default()),
+            ))),
+            Box::new(IRCondition::Comparison(IRComparison::GreaterThan(
+                IRExpression::Value(IRValue::Number(3), Span::default()),
+                IRExpression::Value(IRValue::Number(4), Span::default()),
+            ))),
+        )));
         let target = "test_target".to_string();
-        let variables = VariableOffsets::new();
+        let program = Program::default();
+        let scope = Scope::empty(&program);
 
+        // Jumping if the Or is false is the same as jumping if either Side is true
         let expected = vec![
             Instruction::Move(Register::RAX.to_string(), "1".to_string()),
             Instruction::Push(Register::RAX.to_string()),
             Instruction::Move(Register::RAX.to_string(), "2".to_string()),
             Instruction::Pop(Register::RBX.to_string()),
             Instruction::Cmp(Register::RBX.to_string(), Register::RAX.to_string()),
+            Instruction::Je(target.clone()),
+            Instruction::Move(Register::RAX.to_string(), "3".to_string()),
+            Instruction::Push(Register::RAX.to_string()),
+            Instruction::Move(Register::RAX.to_string(), "4".to_string()),
+            Instruction::Pop(Register::RBX.to_string()),
+            Instruction::Cmp(Register::RBX.to_string(), Register::RAX.to_string()),
             Instruction::Jg(target.clone()),
         ];
 
-        assert_eq!(expected, generate_jump(&comparison, target, &variables));
+        assert_eq!(
+            Ok(expected),
+            generate_inverse_jump(&condition, target, &scope)
+        );
+    }
+
+    #[test]
+    fn comparison_value() {
+        let condition = IRCondition::Comparison(IRComparison::LessThan(
+            IRExpression::Value(IRValue::Number(1), Span::default()),
+            IRExpression::Value(IRValue::Number(2), Span::default()),
+        ));
+        let program = Program::default();
+        let scope = Scope::empty(&program);
+
+        let expected = vec![
+            Instruction::Move(Register::RAX.to_string(), "1".to_string()),
+            Instruction::Push(Register::RAX.to_string()),
+            Instruction::Move(Register::RAX.to_string(), "2".to_string()),
+            Instruction::Pop(Register::RBX.to_string()),
+            Instruction::Cmp(Register::RBX.to_string(), Register::RAX.to_string()),
+            Instruction::Setl("al".to_string()),
+            Instruction::Movzx(Register::RAX.to_string(), "al".to_string()),
+        ];
+
+        assert_eq!(Ok(expected), generate_value(&condition, &scope));
+    }
+
+    #[test]
+    fn unsigned_comparison() {
+        let condition = IRCondition::Comparison(IRComparison::GreaterThan(
+            IRExpression::Variable("u".to_owned(), Span::default()),
+            IRExpression::Value(IRValue::Number(0), Span::default()),
+        ));
+        let target = "test_target".to_string();
+        let program = Program::default();
+        let mut scope = Scope::empty(&program);
+        scope.declare("u", &IRType::U64);
+
+        let compare = || {
+            vec![
+                Instruction::Move(Register::RAX.to_string(), "[rbp - 8]".to_string()),
+                Instruction::Push(Register::RAX.to_string()),
+                Instruction::Move(Register::RAX.to_string(), "0".to_string()),
+                Instruction::Pop(Register::RBX.to_string()),
+                Instruction::Cmp(Register::RBX.to_string(), Register::RAX.to_string()),
+            ]
+        };
+
+        let mut expected = compare();
+        expected.push(Instruction::Jbe(target.clone()));
+        assert_eq!(
+            Ok(expected),
+            generate_inverse_jump(&condition, target, &scope)
+        );
+
+        let mut expected = compare();
+        expected.push(Instruction::Seta("al".to_string()));
+        expected.push(Instruction::Movzx(
+            Register::RAX.to_string(),
+            "al".to_string(),
+        ));
+        assert_eq!(Ok(expected), generate_value(&condition, &scope));
+    }
+
+    #[test]
+    fn inverse_bool_expression() {
+        let condition =
+            IRCondition::Expression(IRExpression::Value(IRValue::Bool(true), Span::default()));
+        let target = "test_target".to_string();
+        let program = Program::default();
+        let scope = Scope::empty(&program);
+
+        let expected = vec![
+            Instruction::Move(Register::RAX.to_string(), "1".to_string()),
+            Instruction::Cmp(Register::RAX.to_string(), "0".to_string()),
+            Instruction::Je(target.clone()),
+        ];
+
+        assert_eq!(
+            Ok(expected),
+            generate_inverse_jump(&condition, target, &scope)
+        );
     }
 }
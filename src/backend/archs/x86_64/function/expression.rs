@@ -1,34 +1,95 @@
+use super::{
+    conditionals, generate_random_string,
+    variables::{self, Scope},
+};
 use crate::{
-    backend::{
-        archs::x86_64::asm::{Instruction, Register},
-        VariableOffsets,
-    },
-    frontend::ir::{IRExpression, IROperation, IRValue},
+    backend::archs::x86_64::asm::{Instruction, Register},
+    error::CompileError,
+    frontend::ir::{IRExpression, IROperation, IRType, IRValue},
 };
 
-// The Result of an evaluated expression will always be placed 'eax'
-pub fn generate(exp: &IRExpression, vars: &VariableOffsets) -> Vec<Instruction> {
+/// The Type of the Value the Expression evaluates to, if it is known.
+/// All the Operations produce an 'i64'
+pub fn value_type(exp: &IRExpression, scope: &Scope) -> Option<IRType> {
+    match exp {
+        IRExpression::Value(IRValue::Number(_), _) => Some(IRType::I64),
+        IRExpression::Value(IRValue::Bool(_), _) => Some(IRType::Bool),
+        IRExpression::Value(IRValue::String(_), _) => Some(IRType::String),
+        IRExpression::Variable(ref name, _) => scope.vars.get(name).map(|var| var.var_type.clone()),
+        IRExpression::Call(ref name, _, _) => {
+            scope.program.return_types.get(name).cloned().flatten()
+        }
+        IRExpression::Operation(_, _, _) => Some(IRType::I64),
+        IRExpression::Condition(_, _) => Some(IRType::Bool),
+        IRExpression::Noop => None,
+    }
+}
+
+/// Checks if the Expression evaluates to a String, which takes up
+/// two Registers instead of one
+fn is_string(exp: &IRExpression, scope: &Scope) -> bool {
+    value_type(exp, scope) == Some(IRType::String)
+}
+
+/// Checks if the Expression evaluates to a 'u64', which has to be compared
+/// and divided as unsigned. All the smaller Integers are extended to a
+/// positive 'i64', so they work with the signed Instructions
+pub fn is_u64(exp: &IRExpression, scope: &Scope) -> bool {
+    match exp {
+        IRExpression::Variable(ref name, _) => scope
+            .vars
+            .get(name)
+            .is_some_and(|var| var.var_type == IRType::U64),
+        IRExpression::Call(ref name, _, _) => {
+            scope.program.return_types.get(name) == Some(&Some(IRType::U64))
+        }
+        IRExpression::Operation(_, ref exps, _) => exps.iter().any(|exp| is_u64(exp, scope)),
+        _ => false,
+    }
+}
+
+// The Result of an evaluated expression will always be placed 'eax',
+// for Strings the Pointer is placed in 'rax' and the Length in 'rdx'
+pub fn generate(exp: &IRExpression, scope: &Scope) -> Result<Vec<Instruction>, CompileError> {
     let mut result = Vec::new();
 
     let target = Register::RAX.to_string();
     match exp {
-        &IRExpression::Value(ref ir_value) => match ir_value {
+        IRExpression::Value(ref ir_value, _) => match ir_value {
             IRValue::Number(ref value) => {
                 result.push(Instruction::Move(target, format!("{}", value)));
             }
+            IRValue::Bool(ref value) => {
+                result.push(Instruction::Move(target, format!("{}", *value as u8)));
+            }
+            IRValue::String(ref value) => {
+                let label = scope.program.string_label(value);
+                result.push(Instruction::Lea(target, format!("[rel {}]", label)));
+                result.push(Instruction::Move(
+                    Register::RDX.to_string(),
+                    format!("{}", value.len()),
+                ));
+            }
         },
-        &IRExpression::Variable(ref var_name) => {
-            let source_offset = vars.get(var_name).unwrap();
-            let source = format!("[rbp - {}]", source_offset);
-            result.push(Instruction::Move(target, source));
+        IRExpression::Variable(ref var_name, ref span) => {
+            let var = match scope.vars.get(var_name) {
+                Some(v) => v,
+                None => {
+                    return Err(CompileError::codegen(
+                        format!("Cant find variable: {:?}", var_name),
+                        *span,
+                    ));
+                }
+            };
+            result.append(&mut variables::load(var, Register::RAX));
         }
-        &IRExpression::Operation(ref operation, ref other_exp) => {
-            let first = other_exp.get(0).unwrap();
+        IRExpression::Operation(ref operation, ref other_exp, _) => {
+            let first = other_exp.first().unwrap();
             let second = other_exp.get(1).unwrap();
 
-            result.append(&mut generate(first, vars));
+            result.append(&mut generate(first, scope)?);
             result.push(Instruction::Push(Register::RAX.to_string()));
-            result.append(&mut generate(second, vars));
+            result.append(&mut generate(second, scope)?);
 
             result.push(Instruction::Move(
                 Register::RBX.to_string(),
@@ -37,35 +98,243 @@ pub fn generate(exp: &IRExpression, vars: &VariableOffsets) -> Vec<Instruction>
             result.push(Instruction::Pop(Register::RAX.to_string()));
 
             match operation {
-                &IROperation::Add => {
+                IROperation::Add => {
                     result.push(Instruction::Add(
                         Register::RAX.to_string(),
                         Register::RBX.to_string(),
                     ));
                 }
-                &IROperation::Sub => {
+                IROperation::Sub => {
                     result.push(Instruction::Sub(
                         Register::RAX.to_string(),
                         Register::RBX.to_string(),
                     ));
                 }
-                _ => {
-                    println!("Unknown OP: {:?}", operation);
+                IROperation::Multiply => {
+                    result.push(Instruction::Imul(
+                        Register::RAX.to_string(),
+                        Register::RBX.to_string(),
+                    ));
+                }
+                IROperation::Divide | IROperation::Modulo
+                    if is_u64(first, scope) || is_u64(second, scope) =>
+                {
+                    // div divides 'rdx:rax', where 'rdx' has to be cleared
+                    result.push(Instruction::Xor(
+                        Register::RDX.sized(4).to_string(),
+                        Register::RDX.sized(4).to_string(),
+                    ));
+                    result.push(Instruction::Div(Register::RBX.to_string()));
+
+                    if *operation == IROperation::Modulo {
+                        result.push(Instruction::Move(
+                            Register::RAX.to_string(),
+                            Register::RDX.to_string(),
+                        ));
+                    }
+                }
+                IROperation::Divide | IROperation::Modulo => {
+                    let random_id = generate_random_string(10);
+                    let divide_target = format!("{}_DIV", random_id);
+                    let end_target = format!("{}_END", random_id);
+
+                    // Dividing the smallest Number by -1 overflows, which would
+                    // trap in idiv, so it is handled separately to wrap around
+                    // like every other Operation
+                    result.push(Instruction::Cmp(
+                        Register::RBX.to_string(),
+                        "-1".to_string(),
+                    ));
+                    result.push(Instruction::Jne(divide_target.clone()));
+                    match operation {
+                        IROperation::Divide => {
+                            result.push(Instruction::Neg(Register::RAX.to_string()))
+                        }
+                        _ => result.push(Instruction::Move(
+                            Register::RAX.to_string(),
+                            "0".to_string(),
+                        )),
+                    };
+                    result.push(Instruction::Jmp(end_target.clone()));
+
+                    // idiv divides 'rdx:rax', so 'rdx' needs to hold the
+                    // sign-extension of 'rax' instead of whatever was left in it.
+                    // The Quotient is placed in 'rax' and the Remainder in 'rdx'
+                    result.push(Instruction::Label(divide_target));
+                    result.push(Instruction::Cqo);
+                    result.push(Instruction::Idiv(Register::RBX.to_string()));
+
+                    if *operation == IROperation::Modulo {
+                        result.push(Instruction::Move(
+                            Register::RAX.to_string(),
+                            Register::RDX.to_string(),
+                        ));
+                    }
+
+                    result.push(Instruction::Label(end_target));
                 }
             };
         }
-        &IRExpression::Call(ref func_name, ref exp) => {
-            for tmp_exp in exp.iter().rev() {
-                result.append(&mut generate(tmp_exp, vars));
+        IRExpression::Call(ref func_name, ref exp, _) => {
+            let strings: Vec<bool> = exp.iter().map(|arg| is_string(arg, scope)).collect();
+
+            // Strings are pushed as the Length followed by the Pointer,
+            // so that the Pointer comes first on the Stack
+            for (tmp_exp, string) in exp.iter().zip(strings.iter()).rev() {
+                result.append(&mut generate(tmp_exp, scope)?);
+                if *string {
+                    result.push(Instruction::Push(Register::RDX.to_string()));
+                }
                 result.push(Instruction::Push(Register::RAX.to_string()));
             }
-            result.push(Instruction::Call(func_name.clone()));
-            for _ in exp.iter() {
-                result.push(Instruction::Add(Register::RSP.to_string(), "8".to_owned()));
+
+            // The Builtin to print Strings is a different one than for Numbers
+            let target = match (func_name.as_str(), strings.first()) {
+                ("print", Some(true)) => "print_string".to_owned(),
+                _ => func_name.clone(),
+            };
+            result.push(Instruction::Call(target));
+
+            for string in strings {
+                let size = match string {
+                    true => "16",
+                    false => "8",
+                };
+                result.push(Instruction::Add(Register::RSP.to_string(), size.to_owned()));
             }
         }
-        &IRExpression::Noop => {}
+        IRExpression::Condition(ref cond, _) => {
+            result.append(&mut conditionals::generate_value(cond, scope)?);
+        }
+        IRExpression::Noop => {}
     };
 
-    result
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::backend::archs::x86_64::function::Program;
+
+    use crate::frontend::Span;
+
+    fn operation(op: IROperation) -> IRExpression {
+        IRExpression::Operation(
+            op,
+            vec![
+                IRExpression::Value(IRValue::Number(7), Span::default()),
+                IRExpression::Value(IRValue::Number(3), Span::default()),
+            ],
+            Span::default(),
+        )
+    }
+
+    fn operands() -> Vec<Instruction> {
+        vec![
+            Instruction::Move(Register::RAX.to_string(), "7".to_string()),
+            Instruction::Push(Register::RAX.to_string()),
+            Instruction::Move(Register::RAX.to_string(), "3".to_string()),
+            Instruction::Move(Register::RBX.to_string(), Register::RAX.to_string()),
+            Instruction::Pop(Register::RAX.to_string()),
+        ]
+    }
+
+    #[test]
+    fn multiply() {
+        let mut expected = operands();
+        expected.push(Instruction::Imul(
+            Register::RAX.to_string(),
+            Register::RBX.to_string(),
+        ));
+
+        assert_eq!(
+            Ok(expected),
+            generate(
+                &operation(IROperation::Multiply),
+                &Scope::empty(&Program::default())
+            )
+        );
+    }
+
+    #[test]
+    fn modulo() {
+        let result = generate(
+            &operation(IROperation::Modulo),
+            &Scope::empty(&Program::default()),
+        )
+        .unwrap();
+
+        // The Labels are random, so only the Instructions around them are checked
+        assert_eq!(operands()[..], result[..5]);
+        assert_eq!(
+            Instruction::Cmp(Register::RBX.to_string(), "-1".to_string()),
+            result[5]
+        );
+        assert_eq!(
+            Instruction::Move(Register::RAX.to_string(), "0".to_string()),
+            result[7]
+        );
+        assert_eq!(
+            vec![
+                Instruction::Cqo,
+                Instruction::Idiv(Register::RBX.to_string()),
+                Instruction::Move(Register::RAX.to_string(), Register::RDX.to_string()),
+            ],
+            result[10..13]
+        );
+    }
+
+    #[test]
+    fn unsigned_divide() {
+        let program = Program::default();
+        let mut scope = Scope::empty(&program);
+        scope.declare("u", &IRType::U64);
+        let exp = IRExpression::Operation(
+            IROperation::Divide,
+            vec![
+                IRExpression::Variable("u".to_owned(), Span::default()),
+                IRExpression::Value(IRValue::Number(2), Span::default()),
+            ],
+            Span::default(),
+        );
+
+        let expected = vec![
+            Instruction::Move(Register::RAX.to_string(), "[rbp - 8]".to_string()),
+            Instruction::Push(Register::RAX.to_string()),
+            Instruction::Move(Register::RAX.to_string(), "2".to_string()),
+            Instruction::Move(Register::RBX.to_string(), Register::RAX.to_string()),
+            Instruction::Pop(Register::RAX.to_string()),
+            Instruction::Xor("edx".to_string(), "edx".to_string()),
+            Instruction::Div(Register::RBX.to_string()),
+        ];
+
+        assert_eq!(Ok(expected), generate(&exp, &scope));
+    }
+
+    #[test]
+    fn print_string() {
+        let program = Program::default();
+        let exp = IRExpression::Call(
+            "print".to_owned(),
+            vec![IRExpression::Value(
+                IRValue::String("hi".to_owned()),
+                Span::default(),
+            )],
+            Span::default(),
+        );
+
+        let expected = vec![
+            Instruction::Lea(Register::RAX.to_string(), "[rel STR_0]".to_string()),
+            Instruction::Move(Register::RDX.to_string(), "2".to_string()),
+            Instruction::Push(Register::RDX.to_string()),
+            Instruction::Push(Register::RAX.to_string()),
+            Instruction::Call("print_string".to_string()),
+            Instruction::Add(Register::RSP.to_string(), "16".to_string()),
+        ];
+
+        assert_eq!(Ok(expected), generate(&exp, &Scope::empty(&program)));
+        assert_eq!(vec!["hi".to_owned()], *program.strings.borrow());
+    }
 }
@@ -1,135 +1,387 @@
+use super::Program;
 use crate::{
-    backend::VariableOffsets,
-    frontend::ir::{IRFunction, IRNode, IRType},
+    backend::{
+        archs::x86_64::asm::{Instruction, Register},
+        Variable, VariableOffsets,
+    },
+    frontend::ir::{IRFunction, IRType},
 };
 
-pub fn generate_offsets(func: &IRFunction) -> (VariableOffsets, i64) {
-    let mut vars = VariableOffsets::new();
-    let mut param_offset = -16;
-    let mut local_offset = 8;
+/// Rounds the Value up to the next Multiple of the Alignment
+fn align_up(value: i64, alignment: i64) -> i64 {
+    (value + alignment - 1) / alignment * alignment
+}
 
-    for param in func.parameters.iter() {
-        vars.insert(param.name.clone(), param_offset);
+/// The Variables that are visible in the current Block, together
+/// with the Stack-Space that is used by them
+#[derive(Debug, PartialEq, Clone)]
+pub struct Scope<'a> {
+    pub vars: VariableOffsets,
+    /// The Offset of the lowest used Byte of the Stack-Frame
+    pub offset: i64,
+    pub program: &'a Program,
+}
 
-        match param.param_type {
-            IRType::Number => {
-                param_offset -= 8;
-            }
-        };
+impl<'a> Scope<'a> {
+    /// Creates a Scope without any Variables
+    pub fn empty(program: &'a Program) -> Self {
+        Self {
+            vars: VariableOffsets::new(),
+            offset: 0,
+            program,
+        }
     }
 
-    for statement in func.statements.iter() {
-        for part in statement {
-            if let &IRNode::DeclareVariable(ref name, ref var_type) = part {
-                let size = match var_type {
-                    IRType::Number => 8,
-                };
+    /// Creates the outermost Scope of the Function, which only
+    /// contains its Parameters
+    pub fn new(func: &IRFunction, program: &'a Program) -> Self {
+        let mut scope = Self::empty(program);
+        let mut param_offset = -16;
 
-                vars.insert(name.clone(), local_offset);
-                local_offset += size;
+        // Every Parameter is pushed as an entire 8 Byte Register, but
+        // only its lower Bytes are actually used. Strings are pushed as
+        // two Registers, the Pointer and the Length
+        for param in func.parameters.iter() {
+            scope.vars.insert(
+                param.name.clone(),
+                Variable {
+                    offset: param_offset,
+                    var_type: param.param_type.clone(),
+                },
+            );
+            param_offset -= match param.param_type {
+                IRType::String => 16,
+                _ => 8,
             };
         }
+
+        scope
+    }
+
+    /// Allocates a new Slot for the Variable, which shadows any other
+    /// Variable with the same Name, and returns the new Offset
+    pub fn declare(&mut self, name: &str, var_type: &IRType) -> i64 {
+        // Every Variable is aligned to its own Size
+        let size = var_type.size() as i64;
+        self.offset = align_up(self.offset + size, size);
+
+        self.vars.insert(
+            name.to_owned(),
+            Variable {
+                offset: self.offset,
+                var_type: var_type.clone(),
+            },
+        );
+
+        self.offset
+    }
+}
+
+/// The Size of the Stack-Frame that is needed to hold all the Variables,
+/// which keeps the Stack-Pointer aligned to 16 Bytes
+pub fn frame_size(max_offset: i64) -> i64 {
+    align_up(max_offset, 16)
+}
+
+/// The Memory-Operand to access the Variable
+fn address(var: &Variable) -> String {
+    let size_name = match var.var_type.size() {
+        1 => "byte ",
+        2 => "word ",
+        4 => "dword ",
+        _ => "",
+    };
+
+    format!("{}[rbp - {}]", size_name, var.offset)
+}
+
+/// The Memory-Operand for the Length of a String, which is stored
+/// right after its Pointer
+fn length_address(var: &Variable) -> String {
+    format!("[rbp - {}]", var.offset - 8)
+}
+
+/// Loads the Variable into the entire Register, extending it
+/// according to the Signedness of its Type. The Length of a String
+/// is always loaded into 'rdx'
+pub fn load(var: &Variable, target: Register) -> Vec<Instruction> {
+    let source = address(var);
+    let instr = match (&var.var_type, var.var_type.size(), var.var_type.is_signed()) {
+        (IRType::String, _, _) => {
+            return vec![
+                Instruction::Move(target.to_string(), source),
+                Instruction::Move(Register::RDX.to_string(), length_address(var)),
+            ];
+        }
+        (_, 8, _) => Instruction::Move(target.to_string(), source),
+        (_, 4, true) => Instruction::Movsxd(target.to_string(), source),
+        // Writing the lower 32 Bits already clears the upper ones
+        (_, 4, false) => Instruction::Move(target.sized(4).to_string(), source),
+        (_, _, true) => Instruction::Movsx(target.to_string(), source),
+        (_, _, false) => Instruction::Movzx(target.to_string(), source),
+    };
+
+    vec![instr]
+}
+
+/// Truncates the Value in the Register to the Integer-Type and extends it
+/// back to the entire Register, according to the Signedness of the Type
+pub fn narrow(var_type: &IRType, register: Register) -> Vec<Instruction> {
+    if !var_type.is_integer() {
+        return vec![];
     }
 
-    (vars, local_offset)
+    let target = register.to_string();
+    let source = register.sized(var_type.size()).to_string();
+    let instr = match (var_type.size(), var_type.is_signed()) {
+        (8, _) => return vec![],
+        (4, true) => Instruction::Movsxd(target, source),
+        // Writing the lower 32 Bits already clears the upper ones
+        (4, false) => Instruction::Move(source.clone(), source),
+        (_, true) => Instruction::Movsx(target, source),
+        (_, false) => Instruction::Movzx(target, source),
+    };
+
+    vec![instr]
+}
+
+/// Stores the lower Part of the Register, that fits into the Variable.
+/// The Length of a String is always taken from 'rdx'
+pub fn store(var: &Variable, source: Register) -> Vec<Instruction> {
+    match var.var_type {
+        IRType::String => vec![
+            Instruction::Move(format!("[rbp - {}]", var.offset), source.to_string()),
+            Instruction::Move(length_address(var), Register::RDX.to_string()),
+        ],
+        _ => vec![Instruction::Move(
+            format!("[rbp - {}]", var.offset),
+            source.sized(var.var_type.size()).to_string(),
+        )],
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::frontend::ir::{IRExpression, IRParameter};
+    use crate::frontend::{ir::IRParameter, Span};
 
-    #[test]
-    fn no_variables() {
-        let function = IRFunction {
+    fn function(parameters: Vec<IRParameter>) -> IRFunction {
+        IRFunction {
             name: "test".to_owned(),
             return_type: None,
-            parameters: vec![],
-            statements: vec![vec![IRNode::SingleExpression(IRExpression::Call(
-                "test_func".to_owned(),
-                vec![],
-            ))]],
-        };
+            parameters,
+            statements: vec![],
+            doc: None,
+            span: Span::default(),
+        }
+    }
 
-        let expected_vars = VariableOffsets::new();
-        let expected_offset = 8;
+    fn param(name: &str) -> IRParameter {
+        IRParameter {
+            name: name.to_owned(),
+            param_type: IRType::I64,
+            span: Span::default(),
+        }
+    }
 
-        let (result_vars, result_offset) = generate_offsets(&function);
+    #[test]
+    fn no_variables() {
+        let program = Program::default();
+        let scope = Scope::new(&function(vec![]), &program);
 
-        assert_eq!(expected_vars, result_vars);
-        assert_eq!(expected_offset, result_offset);
+        assert_eq!(VariableOffsets::new(), scope.vars);
+        assert_eq!(0, frame_size(scope.offset));
     }
 
     #[test]
     fn one_variable() {
-        let function = IRFunction {
-            name: "test".to_owned(),
-            return_type: None,
-            parameters: vec![],
-            statements: vec![vec![IRNode::DeclareVariable(
-                "test_var".to_owned(),
-                IRType::Number,
-            )]],
-        };
+        let program = Program::default();
+        let mut scope = Scope::new(&function(vec![]), &program);
+        scope.declare("test_var", &IRType::I64);
 
         let mut expected_vars = VariableOffsets::new();
-        expected_vars.insert("test_var".to_owned(), 8);
-        let expected_offset = 16;
-
-        let (result_vars, result_offset) = generate_offsets(&function);
+        expected_vars.insert(
+            "test_var".to_owned(),
+            Variable {
+                offset: 8,
+                var_type: IRType::I64,
+            },
+        );
 
-        assert_eq!(expected_vars, result_vars);
-        assert_eq!(expected_offset, result_offset);
+        assert_eq!(expected_vars, scope.vars);
+        assert_eq!(16, frame_size(scope.offset));
     }
 
     #[test]
     fn one_param() {
-        let function = IRFunction {
-            name: "test".to_owned(),
-            return_type: None,
-            parameters: vec![IRParameter {
-                name: "test_param".to_owned(),
-                param_type: IRType::Number,
-            }],
-            statements: vec![],
-        };
+        let program = Program::default();
+        let scope = Scope::new(&function(vec![param("test_param")]), &program);
 
         let mut expected_vars = VariableOffsets::new();
-        expected_vars.insert("test_param".to_owned(), -16);
-        let expected_offset = 8;
-
-        let (result_vars, result_offset) = generate_offsets(&function);
+        expected_vars.insert(
+            "test_param".to_owned(),
+            Variable {
+                offset: -16,
+                var_type: IRType::I64,
+            },
+        );
 
-        assert_eq!(expected_vars, result_vars);
-        assert_eq!(expected_offset, result_offset);
+        assert_eq!(expected_vars, scope.vars);
+        assert_eq!(0, frame_size(scope.offset));
     }
+
     #[test]
     fn two_params() {
-        let function = IRFunction {
-            name: "test".to_owned(),
-            return_type: None,
-            parameters: vec![
-                IRParameter {
-                    name: "test_param1".to_owned(),
-                    param_type: IRType::Number,
-                },
-                IRParameter {
-                    name: "test_param2".to_owned(),
-                    param_type: IRType::Number,
-                },
-            ],
-            statements: vec![],
-        };
+        let program = Program::default();
+        let scope = Scope::new(
+            &function(vec![param("test_param1"), param("test_param2")]),
+            &program,
+        );
 
         let mut expected_vars = VariableOffsets::new();
-        expected_vars.insert("test_param1".to_owned(), -16);
-        expected_vars.insert("test_param2".to_owned(), -24);
-        let expected_offset = 8;
+        expected_vars.insert(
+            "test_param1".to_owned(),
+            Variable {
+                offset: -16,
+                var_type: IRType::I64,
+            },
+        );
+        expected_vars.insert(
+            "test_param2".to_owned(),
+            Variable {
+                offset: -24,
+                var_type: IRType::I64,
+            },
+        );
 
-        let (result_vars, result_offset) = generate_offsets(&function);
+        assert_eq!(expected_vars, scope.vars);
+        assert_eq!(0, frame_size(scope.offset));
+    }
+
+    #[test]
+    fn mixed_sizes() {
+        let program = Program::default();
+        let mut scope = Scope::new(&function(vec![]), &program);
 
-        assert_eq!(expected_vars, result_vars);
-        assert_eq!(expected_offset, result_offset);
+        let offsets = vec![
+            scope.declare("a", &IRType::I8),
+            scope.declare("b", &IRType::U32),
+            scope.declare("c", &IRType::I16),
+            scope.declare("d", &IRType::U64),
+        ];
+
+        assert_eq!(vec![1, 8, 10, 24], offsets);
+        assert_eq!(32, frame_size(scope.offset));
+    }
+
+    #[test]
+    fn shadowing() {
+        let program = Program::default();
+        let mut outer = Scope::new(&function(vec![param("x")]), &program);
+        outer.declare("y", &IRType::I64);
+
+        let mut inner = outer.clone();
+        inner.declare("x", &IRType::U8);
+
+        assert_eq!(9, inner.vars["x"].offset);
+        assert_eq!(-16, outer.vars["x"].offset);
+        assert_eq!(8, outer.offset);
+    }
+
+    #[test]
+    fn sized_load_and_store() {
+        let var = |var_type: IRType| Variable {
+            offset: 8,
+            var_type,
+        };
+
+        assert_eq!(
+            vec![Instruction::Movsx(
+                "rax".to_owned(),
+                "byte [rbp - 8]".to_owned()
+            )],
+            load(&var(IRType::I8), Register::RAX)
+        );
+        assert_eq!(
+            vec![Instruction::Movzx(
+                "rax".to_owned(),
+                "word [rbp - 8]".to_owned()
+            )],
+            load(&var(IRType::U16), Register::RAX)
+        );
+        assert_eq!(
+            vec![Instruction::Movsxd(
+                "rax".to_owned(),
+                "dword [rbp - 8]".to_owned()
+            )],
+            load(&var(IRType::I32), Register::RAX)
+        );
+        assert_eq!(
+            vec![Instruction::Move(
+                "eax".to_owned(),
+                "dword [rbp - 8]".to_owned()
+            )],
+            load(&var(IRType::U32), Register::RAX)
+        );
+        assert_eq!(
+            vec![Instruction::Move("rax".to_owned(), "[rbp - 8]".to_owned())],
+            load(&var(IRType::I64), Register::RAX)
+        );
+        assert_eq!(
+            vec![Instruction::Move("[rbp - 8]".to_owned(), "ax".to_owned())],
+            store(&var(IRType::I16), Register::RAX)
+        );
+    }
+
+    #[test]
+    fn narrowing() {
+        assert_eq!(
+            vec![Instruction::Movzx("rax".to_owned(), "al".to_owned())],
+            narrow(&IRType::U8, Register::RAX)
+        );
+        assert_eq!(
+            vec![Instruction::Movsx("rax".to_owned(), "ax".to_owned())],
+            narrow(&IRType::I16, Register::RAX)
+        );
+        assert_eq!(
+            vec![Instruction::Movsxd("rax".to_owned(), "eax".to_owned())],
+            narrow(&IRType::I32, Register::RAX)
+        );
+        assert_eq!(
+            vec![Instruction::Move("eax".to_owned(), "eax".to_owned())],
+            narrow(&IRType::U32, Register::RAX)
+        );
+        assert_eq!(
+            Vec::<Instruction>::new(),
+            narrow(&IRType::I64, Register::RAX)
+        );
+        assert_eq!(
+            Vec::<Instruction>::new(),
+            narrow(&IRType::Bool, Register::RAX)
+        );
+    }
+
+    #[test]
+    fn string_load_and_store() {
+        let string = Variable {
+            offset: 16,
+            var_type: IRType::String,
+        };
+
+        assert_eq!(
+            vec![
+                Instruction::Move("rax".to_owned(), "[rbp - 16]".to_owned()),
+                Instruction::Move("rdx".to_owned(), "[rbp - 8]".to_owned()),
+            ],
+            load(&string, Register::RAX)
+        );
+        assert_eq!(
+            vec![
+                Instruction::Move("[rbp - 16]".to_owned(), "rax".to_owned()),
+                Instruction::Move("[rbp - 8]".to_owned(), "rdx".to_owned()),
+            ],
+            store(&string, Register::RAX)
+        );
     }
 }
@@ -1,10 +1,12 @@
 use crate::backend::archs::x86_64::{asm, builtin, function};
-use crate::{backend::traits, frontend::ir::IRFunction};
+use crate::{backend::traits, error::CompileError, frontend::ir::IRFunction};
 
 pub type X86_64 = ();
 
 impl traits::Arch for X86_64 {
-    fn generate_asm_string(ir: std::collections::HashMap<String, IRFunction>) -> String {
+    fn generate_asm_string(
+        ir: std::collections::HashMap<String, IRFunction>,
+    ) -> Result<String, CompileError> {
         let mut final_asm = Vec::new();
 
         final_asm.push(asm::Instruction::Section(".text".to_owned()));
@@ -13,8 +15,9 @@ impl traits::Arch for X86_64 {
         // First generate all the Builtin code
         final_asm.append(&mut builtin::generate_builtins());
 
-        for (_, func) in ir {
-            final_asm.append(&mut function::generate_function(&func));
+        let program = function::Program::new(&ir);
+        for func in ir.values() {
+            final_asm.append(&mut function::generate_function(func, &program)?);
         }
 
         final_asm.push(asm::Instruction::Label("_start".to_owned()));
@@ -23,6 +26,13 @@ impl traits::Arch for X86_64 {
         final_asm.push(asm::Instruction::Xor("rdi".to_owned(), "rdi".to_owned()));
         final_asm.push(asm::Instruction::Syscall);
 
-        asm::format(&final_asm)
+        // All the String-Literals that were used by the Functions
+        final_asm.push(asm::Instruction::Section(".rodata".to_owned()));
+        final_asm.append(&mut program.generate_data());
+
+        final_asm.push(asm::Instruction::Section(".bss".to_owned()));
+        final_asm.append(&mut builtin::generate_data());
+
+        Ok(asm::format(&final_asm))
     }
 }
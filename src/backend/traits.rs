@@ -1,5 +1,7 @@
-use crate::frontend::ir::IRFunction;
+use crate::{error::CompileError, frontend::ir::IRFunction};
 
 pub trait Arch {
-    fn generate_asm_string(ir: std::collections::HashMap<String, IRFunction>) -> String;
+    fn generate_asm_string(
+        ir: std::collections::HashMap<String, IRFunction>,
+    ) -> Result<String, CompileError>;
 }
@@ -0,0 +1,174 @@
+use facompiler::optimizer::{OptLevel, Pass};
+
+pub const USAGE: &str = "Usage: facompiler [OPTIONS] <INPUT>...
+
+Options:
+  -o <FILE>        Write the output to <FILE>
+  --emit=<STAGE>   The stage to output: tokens, ir, ir-opt or asm (default: asm)
+  -O0, -O1, -O2    The optimization level (default: -O1)
+  --dump-ir-after=<PASS>
+                   Print the IR to stderr after the optimization pass <PASS> ran
+  -q, --quiet      Don't print any status messages
+  -h, --help       Print this help message
+";
+
+/// The Stage of the Compilation whose Result should be written out
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Emit {
+    Tokens,
+    IR,
+    OptimizedIR,
+    Asm,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub inputs: Vec<String>,
+    pub output: Option<String>,
+    pub emit: Emit,
+    pub opt_level: OptLevel,
+    pub dump_ir_after: Option<Pass>,
+    pub quiet: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Help,
+    Compile(Options),
+}
+
+fn parse_emit(stage: &str) -> Result<Emit, String> {
+    match stage {
+        "tokens" => Ok(Emit::Tokens),
+        "ir" => Ok(Emit::IR),
+        "ir-opt" => Ok(Emit::OptimizedIR),
+        "asm" => Ok(Emit::Asm),
+        _ => Err(format!("Unknown emit stage '{}'", stage)),
+    }
+}
+
+/// Parses the Command-Line Arguments, without the Name of the Program itself
+pub fn parse_args<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut options = Options {
+        inputs: Vec::new(),
+        output: None,
+        emit: Emit::Asm,
+        opt_level: OptLevel::default(),
+        dump_ir_after: None,
+        quiet: false,
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-q" | "--quiet" => options.quiet = true,
+            "-O0" => options.opt_level = OptLevel::O0,
+            "-O1" => options.opt_level = OptLevel::O1,
+            "-O2" => options.opt_level = OptLevel::O2,
+            "-o" => match args.next() {
+                Some(path) => options.output = Some(path),
+                None => return Err("Missing path after '-o'".to_owned()),
+            },
+            _ if arg.starts_with("--emit=") => {
+                options.emit = parse_emit(&arg["--emit=".len()..])?;
+            }
+            _ if arg.starts_with("--dump-ir-after=") => {
+                let name = &arg["--dump-ir-after=".len()..];
+                match Pass::from_name(name) {
+                    Some(pass) => options.dump_ir_after = Some(pass),
+                    None => return Err(format!("Unknown optimization pass '{}'", name)),
+                };
+            }
+            _ if arg.starts_with('-') => return Err(format!("Unknown option '{}'", arg)),
+            _ => options.inputs.push(arg),
+        };
+    }
+
+    if options.inputs.is_empty() {
+        return Err("No input files".to_owned());
+    }
+
+    Ok(Command::Compile(options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn only_input() {
+        let expected = Options {
+            inputs: vec!["test.cl".to_owned()],
+            output: None,
+            emit: Emit::Asm,
+            opt_level: OptLevel::O1,
+            dump_ir_after: None,
+            quiet: false,
+        };
+
+        assert_eq!(
+            Ok(Command::Compile(expected)),
+            parse_args(args(&["test.cl"]))
+        );
+    }
+
+    #[test]
+    fn all_options() {
+        let expected = Options {
+            inputs: vec!["first.cl".to_owned(), "second.cl".to_owned()],
+            output: Some("out.txt".to_owned()),
+            emit: Emit::OptimizedIR,
+            opt_level: OptLevel::O2,
+            dump_ir_after: Some(Pass::ConstProp),
+            quiet: true,
+        };
+
+        assert_eq!(
+            Ok(Command::Compile(expected)),
+            parse_args(args(&[
+                "first.cl",
+                "-o",
+                "out.txt",
+                "--emit=ir-opt",
+                "-O2",
+                "--dump-ir-after=const-prop",
+                "--quiet",
+                "second.cl"
+            ]))
+        );
+    }
+
+    #[test]
+    fn help() {
+        assert_eq!(Ok(Command::Help), parse_args(args(&["test.cl", "--help"])));
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert_eq!(Err("No input files".to_owned()), parse_args(args(&["-O0"])));
+        assert_eq!(
+            Err("Missing path after '-o'".to_owned()),
+            parse_args(args(&["test.cl", "-o"]))
+        );
+        assert_eq!(
+            Err("Unknown emit stage 'bin'".to_owned()),
+            parse_args(args(&["test.cl", "--emit=bin"]))
+        );
+        assert_eq!(
+            Err("Unknown optimization pass 'inline'".to_owned()),
+            parse_args(args(&["test.cl", "--dump-ir-after=inline"]))
+        );
+        assert_eq!(
+            Err("Unknown option '-O3'".to_owned()),
+            parse_args(args(&["test.cl", "-O3"]))
+        );
+    }
+}
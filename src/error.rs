@@ -0,0 +1,112 @@
+use std::fmt;
+
+use crate::frontend::Span;
+
+/// An Error that stops the Compilation of a Program
+#[derive(Debug, PartialEq, Clone)]
+pub enum CompileError {
+    /// The Source-Code could not be split up into valid Tokens
+    Lex { message: String, span: Span },
+    /// The Tokens don't make up a valid Program
+    Parse { message: String, span: Span },
+    /// The Program is syntactically correct, but does not make sense
+    Semantic { message: String, span: Span },
+    /// The Backend is unable to generate Code for the Program
+    Codegen { message: String, span: Span },
+}
+
+impl CompileError {
+    pub fn lex<S: Into<String>>(message: S, span: Span) -> Self {
+        CompileError::Lex {
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn parse<S: Into<String>>(message: S, span: Span) -> Self {
+        CompileError::Parse {
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn semantic<S: Into<String>>(message: S, span: Span) -> Self {
+        CompileError::Semantic {
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn codegen<S: Into<String>>(message: S, span: Span) -> Self {
+        CompileError::Codegen {
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// The human readable Description of the Error
+    pub fn message(&self) -> &str {
+        match self {
+            CompileError::Lex { message, .. }
+            | CompileError::Parse { message, .. }
+            | CompileError::Semantic { message, .. }
+            | CompileError::Codegen { message, .. } => message,
+        }
+    }
+
+    /// The Location in the Source-Code that caused the Error
+    pub fn span(&self) -> Span {
+        match self {
+            CompileError::Lex { span, .. }
+            | CompileError::Parse { span, .. }
+            | CompileError::Semantic { span, .. }
+            | CompileError::Codegen { span, .. } => *span,
+        }
+    }
+
+    /// The Name of the Compilation-Stage that produced the Error
+    pub fn stage(&self) -> &'static str {
+        match self {
+            CompileError::Lex { .. } => "lex",
+            CompileError::Parse { .. } => "parse",
+            CompileError::Semantic { .. } => "semantic",
+            CompileError::Codegen { .. } => "codegen",
+        }
+    }
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {} error: {}",
+            self.span(),
+            self.stage(),
+            self.message()
+        )
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+/// A Problem in the Program that does not stop the Compilation
+#[derive(Debug, PartialEq, Clone)]
+pub struct CompileWarning {
+    pub message: String,
+    pub span: Span,
+}
+
+impl CompileWarning {
+    pub fn new<S: Into<String>>(message: S, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+impl fmt::Display for CompileWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] warning: {}", self.span, self.message)
+    }
+}
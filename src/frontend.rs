@@ -1,12 +1,47 @@
 use self::ir::IRFunction;
+use crate::error::CompileError;
 
+pub mod diagnostics;
 pub mod ir;
-mod lexer;
+pub mod lexer;
+pub mod sema;
+mod source_map;
+pub use source_map::{SourceFile, SourceMap};
+mod span;
+pub use span::Span;
 
-pub fn parse(content: String) -> std::collections::HashMap<String, IRFunction> {
-    let tokens = lexer::tokenize(content);
+pub fn tokenize(file: &SourceFile) -> Result<Vec<lexer::SpannedToken>, CompileError> {
+    lexer::tokenize(&file.content, file.offset)
+}
+
+pub fn parse(
+    content: String,
+) -> Result<std::collections::HashMap<String, IRFunction>, CompileError> {
+    let tokens = lexer::tokenize(&content, 0)?;
+
+    ir::parse(&tokens)
+}
+
+/// Parses all the Files and combines their Functions into a single Program
+pub fn parse_files(
+    sources: &SourceMap,
+) -> Result<std::collections::HashMap<String, IRFunction>, CompileError> {
+    let mut result = std::collections::HashMap::new();
+
+    for file in sources.files() {
+        let tokens = tokenize(file)?;
+
+        for (name, func) in ir::parse(&tokens)? {
+            if result.contains_key(&name) {
+                return Err(CompileError::semantic(
+                    format!("Function '{}' is defined multiple times", name),
+                    func.span,
+                ));
+            }
 
-    let ir = ir::parse(&tokens).unwrap();
+            result.insert(name, func);
+        }
+    }
 
-    ir
+    Ok(result)
 }
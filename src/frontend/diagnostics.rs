@@ -0,0 +1,300 @@
+use std::io::IsTerminal;
+
+use crate::error::{CompileError, CompileWarning};
+
+use super::Span;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+
+/// How severe a Diagnostic is
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+impl Level {
+    fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warning => "warning",
+        }
+    }
+
+    fn colour(&self) -> &'static str {
+        match self {
+            Level::Error => RED,
+            Level::Warning => YELLOW,
+        }
+    }
+}
+
+/// A Message for the User that points at some Location in the Source-Code
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub code: String,
+    pub message: String,
+    pub span: Span,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+}
+
+/// Only uses Colours for the Output if stderr is actually a Terminal
+pub fn use_colour() -> bool {
+    std::io::stderr().is_terminal()
+}
+
+/// The Width of the Text, with every Tab taking up 4 Columns
+fn display_width(text: &str) -> usize {
+    text.chars().map(|c| if c == '\t' { 4 } else { 1 }).sum()
+}
+
+impl Diagnostic {
+    pub fn new<C, M>(level: Level, code: C, message: M, span: Span) -> Self
+    where
+        C: Into<String>,
+        M: Into<String>,
+    {
+        Self {
+            level,
+            code: code.into(),
+            message: message.into(),
+            span,
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    pub fn with_note<S: Into<String>>(mut self, note: S) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_help<S: Into<String>>(mut self, help: S) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Renders the Diagnostic together with the offending Line
+    /// from the Source-Code
+    pub fn render(&self, file_name: &str, source: &str, colour: bool) -> String {
+        let paint = |style: &'static str| if colour { style } else { "" };
+        let reset = paint(RESET);
+
+        let mut result = format!(
+            "{}{}[{}]{}{}: {}{}\n",
+            paint(self.level.colour()),
+            self.level.name(),
+            self.code,
+            reset,
+            paint(BOLD),
+            self.message,
+            reset
+        );
+
+        let source_line = match self.span.line {
+            0 => None,
+            line => source.lines().nth(line - 1),
+        };
+
+        let gutter = " ".repeat(match source_line {
+            Some(_) => self.span.line.to_string().len(),
+            None => 0,
+        });
+
+        match source_line {
+            Some(line) => {
+                result.push_str(&format!(
+                    "{}{}--> {}{}:{}\n",
+                    gutter,
+                    paint(BLUE),
+                    reset,
+                    file_name,
+                    self.span
+                ));
+                result.push_str(&format!("{} {}|{}\n", gutter, paint(BLUE), reset));
+
+                result.push_str(&format!(
+                    "{}{} |{} {}\n",
+                    paint(BLUE),
+                    self.span.line,
+                    reset,
+                    line.replace('\t', "    ")
+                ));
+
+                let before: String = line.chars().take(self.span.column - 1).collect();
+                // The Span covers Bytes, so the underlined Characters are
+                // the ones that start inside of that Byte-Range
+                let length = self.span.end.saturating_sub(self.span.start);
+                let underlined: String = line[before.len()..]
+                    .char_indices()
+                    .take_while(|(index, _)| *index < length)
+                    .map(|(_, c)| c)
+                    .collect();
+                result.push_str(&format!(
+                    "{} {}|{} {}{}{}{}\n",
+                    gutter,
+                    paint(BLUE),
+                    reset,
+                    " ".repeat(display_width(&before)),
+                    paint(self.level.colour()),
+                    "^".repeat(display_width(&underlined).max(1)),
+                    reset
+                ));
+            }
+            None => {
+                result.push_str(&format!("{}--> {}{}\n", paint(BLUE), reset, file_name));
+            }
+        };
+
+        for note in self.notes.iter() {
+            result.push_str(&format!(
+                "{} {}={} {}note{}: {}\n",
+                gutter,
+                paint(BLUE),
+                reset,
+                paint(BOLD),
+                reset,
+                note
+            ));
+        }
+        if let Some(ref help) = self.help {
+            result.push_str(&format!(
+                "{} {}={} {}help{}: {}\n",
+                gutter,
+                paint(BLUE),
+                reset,
+                paint(BOLD),
+                reset,
+                help
+            ));
+        }
+
+        result
+    }
+}
+
+impl From<&CompileError> for Diagnostic {
+    fn from(err: &CompileError) -> Self {
+        let code = match err {
+            CompileError::Lex { .. } => "E0001",
+            CompileError::Parse { .. } => "E0002",
+            CompileError::Semantic { .. } => "E0003",
+            CompileError::Codegen { .. } => "E0004",
+        };
+
+        let diagnostic = Diagnostic::new(Level::Error, code, err.message(), err.span());
+        match err {
+            CompileError::Codegen { .. } => {
+                diagnostic.with_note("the x86_64 backend was unable to generate code for this")
+            }
+            _ => diagnostic,
+        }
+    }
+}
+
+impl From<&CompileWarning> for Diagnostic {
+    fn from(warning: &CompileWarning) -> Self {
+        Diagnostic::new(Level::Warning, "W0001", &warning.message, warning.span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_error() {
+        let source = "func main() {\n    x = 1;\n}\n";
+        let diagnostic = Diagnostic::new(
+            Level::Error,
+            "E0003",
+            "Unknown variable 'x'",
+            Span::new(18, 19, 2, 5),
+        );
+
+        let expected = "error[E0003]: Unknown variable 'x'
+ --> test.cl:2:5
+  |
+2 |     x = 1;
+  |     ^
+";
+
+        assert_eq!(expected, diagnostic.render("test.cl", source, false));
+    }
+
+    #[test]
+    fn render_notes_and_help() {
+        let source = "number value = 5;";
+        let diagnostic = Diagnostic::new(
+            Level::Warning,
+            "W0001",
+            "Unused variable",
+            Span::new(7, 12, 1, 8),
+        )
+        .with_note("declared here")
+        .with_help("remove it");
+
+        let expected = "warning[W0001]: Unused variable
+ --> test.cl:1:8
+  |
+1 | number value = 5;
+  |        ^^^^^
+  = note: declared here
+  = help: remove it
+";
+
+        assert_eq!(expected, diagnostic.render("test.cl", source, false));
+    }
+
+    #[test]
+    fn render_with_tabs() {
+        let source = "\tprint(x);";
+        let diagnostic = Diagnostic::new(Level::Error, "E0002", "Test", Span::new(7, 8, 1, 8));
+
+        let expected = "error[E0002]: Test
+ --> test.cl:1:8
+  |
+1 |     print(x);
+  |           ^
+";
+
+        assert_eq!(expected, diagnostic.render("test.cl", source, false));
+    }
+
+    #[test]
+    fn render_non_ascii() {
+        let source = "print(\"ü\");";
+        let diagnostic = Diagnostic::new(Level::Error, "E0002", "Test", Span::new(6, 10, 1, 7));
+
+        let expected = "error[E0002]: Test
+ --> test.cl:1:7
+  |
+1 | print(\"ü\");
+  |       ^^^
+";
+
+        assert_eq!(expected, diagnostic.render("test.cl", source, false));
+    }
+
+    #[test]
+    fn render_without_location() {
+        let diagnostic = Diagnostic::new(
+            Level::Error,
+            "E0002",
+            "Unexpected end of input",
+            Span::default(),
+        );
+
+        let expected = "error[E0002]: Unexpected end of input
+--> test.cl
+";
+
+        assert_eq!(expected, diagnostic.render("test.cl", "", false));
+    }
+}
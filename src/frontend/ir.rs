@@ -1,25 +1,99 @@
-use super::lexer::{Primitives, Token};
+use super::lexer::{Comparisons, Primitives, SpannedToken, Token};
+use super::Span;
+use crate::error::CompileError;
 
 mod parse_expression;
 pub use parse_expression::parse_expression;
 
 mod pretty_print;
-pub use pretty_print::pretty_print;
+pub use pretty_print::{pretty_format, pretty_print};
 
 mod parse_arguments;
+mod parse_condition;
 mod parse_inner;
 mod parse_passed_args;
+mod parse_util;
 
 pub type IRIdentifier = String;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum IRType {
-    Number,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    Bool,
+    /// A Pointer to the Bytes of the String together with its Length
+    String,
+}
+
+impl IRType {
+    /// The Size of a Value of this Type in Bytes
+    pub fn size(&self) -> usize {
+        match self {
+            IRType::I8 | IRType::U8 | IRType::Bool => 1,
+            IRType::I16 | IRType::U16 => 2,
+            IRType::I32 | IRType::U32 => 4,
+            IRType::I64 | IRType::U64 => 8,
+            IRType::String => 16,
+        }
+    }
+
+    /// Whether the Type is one of the Integer-Types
+    pub fn is_integer(&self) -> bool {
+        !matches!(self, IRType::Bool | IRType::String)
+    }
+
+    pub fn is_signed(&self) -> bool {
+        matches!(self, IRType::I8 | IRType::I16 | IRType::I32 | IRType::I64)
+    }
+}
+
+impl std::fmt::Display for IRType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            IRType::I8 => "i8",
+            IRType::I16 => "i16",
+            IRType::I32 => "i32",
+            IRType::I64 => "i64",
+            IRType::U8 => "u8",
+            IRType::U16 => "u16",
+            IRType::U32 => "u32",
+            IRType::U64 => "u64",
+            IRType::Bool => "bool",
+            IRType::String => "string",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl From<&Primitives> for IRType {
+    fn from(prim: &Primitives) -> Self {
+        match prim {
+            Primitives::Number | Primitives::I64 => IRType::I64,
+            Primitives::I8 => IRType::I8,
+            Primitives::I16 => IRType::I16,
+            Primitives::I32 => IRType::I32,
+            Primitives::U8 => IRType::U8,
+            Primitives::U16 => IRType::U16,
+            Primitives::U32 => IRType::U32,
+            Primitives::U64 => IRType::U64,
+            Primitives::Bool => IRType::Bool,
+            Primitives::String => IRType::String,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum IRValue {
-    Number(u64),
+    Number(i64),
+    Bool(bool),
+    String(String),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -28,37 +102,135 @@ pub enum IROperation {
     Sub,
     Multiply,
     Divide,
+    Modulo,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum IRExpression {
-    Operation(IROperation, Vec<IRExpression>),
-    Value(IRValue),
-    Variable(IRIdentifier),
-    Call(IRIdentifier, Vec<IRExpression>),
+    Operation(IROperation, Vec<IRExpression>, Span),
+    Value(IRValue, Span),
+    Variable(IRIdentifier, Span),
+    Call(IRIdentifier, Vec<IRExpression>, Span),
+    /// The boolean Result of a Condition
+    Condition(Box<IRCondition>, Span),
     Noop,
 }
 
+impl IRExpression {
+    /// The Location of the entire Expression in the Source-Code
+    pub fn span(&self) -> Span {
+        match self {
+            IRExpression::Operation(_, _, span)
+            | IRExpression::Value(_, span)
+            | IRExpression::Variable(_, span)
+            | IRExpression::Call(_, _, span)
+            | IRExpression::Condition(_, span) => *span,
+            IRExpression::Noop => Span::default(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum IRComparison {
     Equals(IRExpression, IRExpression),
+    NotEquals(IRExpression, IRExpression),
+    LessThan(IRExpression, IRExpression),
+    LessEqual(IRExpression, IRExpression),
     GreaterThan(IRExpression, IRExpression),
+    GreaterEqual(IRExpression, IRExpression),
+}
+
+impl IRComparison {
+    pub fn new(comparison: &Comparisons, left: IRExpression, right: IRExpression) -> Self {
+        match comparison {
+            Comparisons::Equal => IRComparison::Equals(left, right),
+            Comparisons::NotEqual => IRComparison::NotEquals(left, right),
+            Comparisons::LessThan => IRComparison::LessThan(left, right),
+            Comparisons::LessEqual => IRComparison::LessEqual(left, right),
+            Comparisons::GreaterThan => IRComparison::GreaterThan(left, right),
+            Comparisons::GreaterEqual => IRComparison::GreaterEqual(left, right),
+        }
+    }
+
+    /// The left and right Side of the Comparison
+    pub fn operands(&self) -> (&IRExpression, &IRExpression) {
+        match self {
+            IRComparison::Equals(left, right)
+            | IRComparison::NotEquals(left, right)
+            | IRComparison::LessThan(left, right)
+            | IRComparison::LessEqual(left, right)
+            | IRComparison::GreaterThan(left, right)
+            | IRComparison::GreaterEqual(left, right) => (left, right),
+        }
+    }
+}
+
+/// A Condition made up of Comparisons and boolean Expressions, which
+/// are combined using the logical Operators
+#[derive(Debug, PartialEq, Clone)]
+pub enum IRCondition {
+    Comparison(IRComparison),
+    /// A boolean Expression, like a Variable
+    Expression(IRExpression),
+    And(Box<IRCondition>, Box<IRCondition>),
+    Or(Box<IRCondition>, Box<IRCondition>),
+    Not(Box<IRCondition>),
+}
+
+impl IRCondition {
+    /// The Location of the entire Condition in the Source-Code
+    pub fn span(&self) -> Span {
+        match self {
+            IRCondition::Comparison(comp) => {
+                let (left, right) = comp.operands();
+                left.span().merge(&right.span())
+            }
+            IRCondition::Expression(exp) => exp.span(),
+            IRCondition::And(left, right) | IRCondition::Or(left, right) => {
+                left.span().merge(&right.span())
+            }
+            IRCondition::Not(inner) => inner.span(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum IRNode {
-    SingleExpression(IRExpression),
-    DeclareVariable(IRIdentifier, IRType),
-    Assignment(IRIdentifier, IRExpression),
-    Conditional(IRComparison, Vec<Vec<IRNode>>),
-    Loop(IRComparison, Vec<Vec<IRNode>>),
-    Return(Option<IRExpression>),
+    SingleExpression(IRExpression, Span),
+    /// The Name and Type of the new Variable with its optional initial Value
+    DeclareVariable(IRIdentifier, IRType, Option<IRExpression>, Span),
+    Assignment(IRIdentifier, IRExpression, Span),
+    /// The Condition, the Statements if it is true and the optional
+    /// Statements of the Else-Branch
+    Conditional(
+        IRCondition,
+        Vec<Vec<IRNode>>,
+        Option<Vec<Vec<IRNode>>>,
+        Span,
+    ),
+    Loop(IRCondition, Vec<Vec<IRNode>>, Span),
+    Return(Option<IRExpression>, Span),
+}
+
+impl IRNode {
+    /// The Location of the entire Node in the Source-Code
+    pub fn span(&self) -> Span {
+        match self {
+            IRNode::SingleExpression(_, span)
+            | IRNode::DeclareVariable(_, _, _, span)
+            | IRNode::Assignment(_, _, span)
+            | IRNode::Conditional(_, _, _, span)
+            | IRNode::Loop(_, _, span)
+            | IRNode::Return(_, span) => *span,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct IRParameter {
     pub name: IRIdentifier,
     pub param_type: IRType,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -67,61 +239,73 @@ pub struct IRFunction {
     pub return_type: Option<IRType>,
     pub parameters: Vec<IRParameter>,
     pub statements: Vec<Vec<IRNode>>,
+    /// The Text of the Doc-Comments in front of the Function
+    pub doc: Option<String>,
+    /// The Location of the Functions Name
+    pub span: Span,
 }
 
-pub fn parse(tokens: &[Token]) -> Option<std::collections::HashMap<String, IRFunction>> {
+pub fn parse(
+    tokens: &[SpannedToken],
+) -> Result<std::collections::HashMap<String, IRFunction>, CompileError> {
     let mut result = std::collections::HashMap::new();
 
-    let mut iter = tokens.iter().peekable();
+    // The Tokenizer marks everything it could not understand, which
+    // is reported before trying to make sense of the Tokens
+    if let Some(invalid) = tokens.iter().find_map(|token| match token.token {
+        Token::Error(ref message) => Some(CompileError::lex(message.clone(), token.span)),
+        _ => None,
+    }) {
+        return Err(invalid);
+    }
+
+    // Doc-Comments only have a Meaning directly in front of a Function,
+    // everywhere else they are treated like any other Comment
+    let mut attached = Vec::with_capacity(tokens.len());
+    let mut before_function = false;
+    for token in tokens.iter().rev() {
+        match token.token {
+            Token::DocComment(_) if !before_function => continue,
+            Token::DocComment(_) => {}
+            _ => before_function = token.token == Token::Function,
+        };
+        attached.push(token);
+    }
+    attached.reverse();
+
+    let mut docs: Vec<&str> = Vec::new();
+    let mut iter = attached.into_iter().peekable();
     while let Some(current) = iter.next() {
-        match current {
+        match current.token {
+            Token::DocComment(ref text) => docs.push(text),
             Token::Function => {
-                let name = match iter.peek() {
-                    Some(Token::Identifier(name)) => {
-                        iter.next().unwrap();
-                        name.clone()
-                    }
-                    _ => return None,
+                let name_token = parse_util::next(&mut iter)?;
+                let name = match name_token.token {
+                    Token::Identifier(ref name) => name.clone(),
+                    _ => return Err(parse_util::unexpected(name_token, "function name")),
                 };
 
-                match iter.peek() {
-                    Some(Token::OpenParan) => iter.next(),
-                    _ => return None,
-                };
+                parse_util::expect(&mut iter, &Token::OpenParan)?;
 
-                let arguments = parse_arguments::parse(&mut iter);
+                let arguments = parse_arguments::parse(&mut iter)?;
 
-                match iter.peek() {
-                    Some(Token::ClosingParan) => iter.next(),
-                    _ => return None,
-                };
-                let return_type = match iter.peek() {
-                    Some(Token::OpenCurly) => {
-                        iter.next();
-                        None
-                    }
-                    Some(Token::Arrow) => {
-                        iter.next();
-
-                        let prim = match iter.peek() {
-                            Some(Token::Primitive(ref prim)) => {
-                                iter.next().unwrap();
-
-                                match prim {
-                                    Primitives::Number => IRType::Number,
-                                }
-                            }
-                            _ => return None,
-                        };
+                parse_util::expect(&mut iter, &Token::ClosingParan)?;
 
-                        match iter.peek() {
-                            Some(Token::OpenCurly) => iter.next().unwrap(),
-                            _ => return None,
+                let next_token = parse_util::next(&mut iter)?;
+                let return_type = match next_token.token {
+                    Token::OpenCurly => None,
+                    Token::Arrow => {
+                        let type_token = parse_util::next(&mut iter)?;
+                        let prim = match type_token.token {
+                            Token::Primitive(ref prim) => IRType::from(prim),
+                            _ => return Err(parse_util::unexpected(type_token, "return type")),
                         };
 
+                        parse_util::expect(&mut iter, &Token::OpenCurly)?;
+
                         Some(prim)
                     }
-                    _ => return None,
+                    _ => return Err(parse_util::unexpected(next_token, "'{' or '->'")),
                 };
 
                 let inner = parse_inner::inner_parse(&mut iter)?;
@@ -131,14 +315,46 @@ pub fn parse(tokens: &[Token]) -> Option<std::collections::HashMap<String, IRFun
                     return_type,
                     parameters: arguments,
                     statements: inner,
+                    doc: match docs.is_empty() {
+                        true => None,
+                        false => Some(docs.join("\n")),
+                    },
+                    span: name_token.span,
                 };
+                if result.contains_key(&name) {
+                    return Err(CompileError::semantic(
+                        format!("Function '{}' is defined multiple times", name),
+                        func.span,
+                    ));
+                }
+
                 result.insert(name, func);
+                docs.clear();
             }
-            _ => {
-                log::error!("Unexpected: {:?}", current);
-            }
+            Token::EndOfFile => break,
+            _ => return Err(parse_util::unexpected(current, "'func'")),
         };
     }
 
-    Some(result)
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::frontend::lexer::tokenize;
+
+    #[test]
+    fn duplicate_function() {
+        let tokens = tokenize("func a() {}\nfunc a() {}\n", 0).unwrap();
+
+        assert_eq!(
+            Err(CompileError::semantic(
+                "Function 'a' is defined multiple times",
+                Span::new(17, 18, 2, 6)
+            )),
+            parse(&tokens)
+        );
+    }
 }
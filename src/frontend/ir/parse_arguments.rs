@@ -1,117 +1,135 @@
 use std::iter::Peekable;
 
-use crate::frontend::lexer::{Primitives, Token};
+use crate::{
+    error::CompileError,
+    frontend::lexer::{SpannedToken, Token},
+};
 
-use super::{IRParameter, IRType};
+use super::{parse_util, IRParameter, IRType};
 
-// TODO
-// Add support for more than one parameter
-pub fn parse<'a, I>(iter: &mut Peekable<I>) -> Vec<IRParameter>
+/// Parses the Parameter-List of a Function-Definition up until,
+/// but not including, the closing Parenthesis
+pub fn parse<'a, I>(iter: &mut Peekable<I>) -> Result<Vec<IRParameter>, CompileError>
 where
-    I: Iterator<Item = &'a Token>,
+    I: Iterator<Item = &'a SpannedToken>,
 {
     let mut result = Vec::new();
 
-    while let Some(peeked) = iter.peek() {
-        if let Token::ClosingParan = peeked {
+    loop {
+        if let Token::ClosingParan = parse_util::peek(iter)?.token {
             break;
         }
 
-        let next = iter.next().unwrap();
-        match next {
-            Token::Identifier(ref name) => {
-                match iter.peek() {
-                    Some(Token::Colon) => {
-                        iter.next().unwrap();
-                    }
-                    _ => break,
-                };
-
-                match iter.peek() {
-                    Some(Token::Primitive(_)) => {}
-                    _ => break,
-                };
-
-                let param_type = match iter.next().unwrap() {
-                    Token::Primitive(ref prim) => match prim {
-                        Primitives::Number => IRType::Number,
-                    },
-                    _ => break,
-                };
-
-                result.push(IRParameter {
-                    name: name.to_owned(),
-                    param_type,
-                });
-            }
-            Token::Comma => {}
-            _ => {
-                break;
-            }
+        if !result.is_empty() {
+            parse_util::expect(iter, &Token::Comma)?;
+        }
+
+        let name_token = parse_util::next(iter)?;
+        let name = match name_token.token {
+            Token::Identifier(ref name) => name,
+            _ => return Err(parse_util::unexpected(name_token, "parameter name")),
+        };
+
+        parse_util::expect(iter, &Token::Colon)?;
+
+        let type_token = parse_util::next(iter)?;
+        let param_type = match type_token.token {
+            Token::Primitive(ref prim) => IRType::from(prim),
+            _ => return Err(parse_util::unexpected(type_token, "parameter type")),
         };
+
+        result.push(IRParameter {
+            name: name.to_owned(),
+            param_type,
+            span: name_token.span.merge(&type_token.span),
+        });
     }
 
-    result
+    Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::frontend::lexer::Primitives;
+    use crate::frontend::{
+        lexer::{with_default_spans, Primitives},
+        Span,
+    };
 
     #[test]
     fn no_params() {
-        let tokens = vec![Token::ClosingParan, Token::OpenCurly];
+        let tokens = with_default_spans(vec![Token::ClosingParan, Token::OpenCurly]);
 
         let expected: Vec<IRParameter> = vec![];
 
-        assert_eq!(expected, parse(&mut tokens.iter().peekable()));
+        assert_eq!(Ok(expected), parse(&mut tokens.iter().peekable()));
     }
 
     #[test]
     fn one_param() {
-        let tokens = vec![
+        let tokens = with_default_spans(vec![
             Token::Identifier("test1".to_owned()),
             Token::Colon,
             Token::Primitive(Primitives::Number),
             Token::ClosingParan,
             Token::OpenCurly,
-        ];
+        ]);
 
         let expected = vec![IRParameter {
             name: "test1".to_owned(),
-            param_type: IRType::Number,
+            param_type: IRType::I64,
+            span: Span::default(),
         }];
 
-        assert_eq!(expected, parse(&mut tokens.iter().peekable()));
+        assert_eq!(Ok(expected), parse(&mut tokens.iter().peekable()));
     }
 
     #[test]
     fn two_params() {
-        let tokens = vec![
+        let tokens = with_default_spans(vec![
             Token::Identifier("test1".to_owned()),
             Token::Colon,
             Token::Primitive(Primitives::Number),
             Token::Comma,
             Token::Identifier("test2".to_owned()),
             Token::Colon,
-            Token::Primitive(Primitives::Number),
+            Token::Primitive(Primitives::U8),
             Token::ClosingParan,
             Token::OpenCurly,
-        ];
+        ]);
 
         let expected = vec![
             IRParameter {
                 name: "test1".to_owned(),
-                param_type: IRType::Number,
+                param_type: IRType::I64,
+                span: Span::default(),
             },
             IRParameter {
                 name: "test2".to_owned(),
-                param_type: IRType::Number,
+                param_type: IRType::U8,
+                span: Span::default(),
             },
         ];
 
-        assert_eq!(expected, parse(&mut tokens.iter().peekable()));
+        assert_eq!(Ok(expected), parse(&mut tokens.iter().peekable()));
+    }
+
+    #[test]
+    fn missing_type() {
+        let tokens = with_default_spans(vec![
+            Token::Identifier("test1".to_owned()),
+            Token::Colon,
+            Token::ClosingParan,
+            Token::OpenCurly,
+        ]);
+
+        assert_eq!(
+            Err(CompileError::parse(
+                "Expected parameter type, found ')'",
+                Span::default()
+            )),
+            parse(&mut tokens.iter().peekable())
+        );
     }
 }
@@ -0,0 +1,320 @@
+use std::iter::Peekable;
+
+use crate::{
+    error::CompileError,
+    frontend::lexer::{SpannedToken, Token},
+};
+
+use super::{
+    parse_expression::{continue_binary, parse_binary},
+    parse_util, IRComparison, IRCondition, IRExpression,
+};
+
+/// Parentheses can group either a Condition or an Expression, which is
+/// only known once the Content of them has been parsed
+enum Operand {
+    Expression(IRExpression),
+    Condition(IRCondition),
+}
+
+impl Operand {
+    /// A plain Expression is used as a boolean Condition
+    fn into_condition(self) -> IRCondition {
+        match self {
+            Operand::Condition(cond) => cond,
+            Operand::Expression(exp) => IRCondition::Expression(exp),
+        }
+    }
+
+    /// A Condition is used as a boolean Value
+    fn into_expression(self) -> IRExpression {
+        match self {
+            Operand::Expression(exp) => exp,
+            Operand::Condition(cond) => {
+                let span = cond.span();
+                IRExpression::Condition(Box::new(cond), span)
+            }
+        }
+    }
+}
+
+/// Parses a single Operand, which is either an Expression or anything
+/// in Parentheses
+fn parse_operand<'a, I>(iter: &mut Peekable<I>) -> Result<Operand, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    match iter.peek().map(|t| &t.token) {
+        Some(Token::OpenParan) => {
+            // Advance the iterator
+            iter.next().unwrap();
+
+            let inner = parse_or(iter)?;
+            parse_util::expect(iter, &Token::ClosingParan)?;
+
+            match inner {
+                // The Expression in Parentheses might only be the first
+                // Operand of a larger Expression, like '(a + 1) * 2'
+                Operand::Expression(exp) => Ok(Operand::Expression(continue_binary(iter, exp, 0)?)),
+                Operand::Condition(cond) => Ok(Operand::Condition(cond)),
+            }
+        }
+        _ => Ok(Operand::Expression(parse_binary(iter, 0)?)),
+    }
+}
+
+fn parse_comparison<'a, I>(iter: &mut Peekable<I>) -> Result<Operand, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    let left = match parse_operand(iter)? {
+        Operand::Expression(exp) => exp,
+        Operand::Condition(cond) => return Ok(Operand::Condition(cond)),
+    };
+
+    let next = parse_util::peek(iter)?;
+    let comparison = match next.token {
+        Token::Comparison(ref comparison) => comparison,
+        _ => return Ok(Operand::Expression(left)),
+    };
+
+    // Advance the iterator
+    iter.next().unwrap();
+
+    let right = match parse_operand(iter)? {
+        Operand::Expression(exp) => exp,
+        Operand::Condition(_) => {
+            return Err(CompileError::parse(
+                "Only expressions can be compared",
+                next.span,
+            ));
+        }
+    };
+
+    Ok(Operand::Condition(IRCondition::Comparison(
+        IRComparison::new(comparison, left, right),
+    )))
+}
+
+fn parse_not<'a, I>(iter: &mut Peekable<I>) -> Result<Operand, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    match iter.peek().map(|t| &t.token) {
+        Some(Token::Not) => {
+            // Advance the iterator
+            iter.next().unwrap();
+
+            let inner = parse_not(iter)?.into_condition();
+            Ok(Operand::Condition(IRCondition::Not(Box::new(inner))))
+        }
+        _ => parse_comparison(iter),
+    }
+}
+
+fn parse_and<'a, I>(iter: &mut Peekable<I>) -> Result<Operand, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    let mut left = parse_not(iter)?;
+
+    while let Some(Token::And) = iter.peek().map(|t| &t.token) {
+        // Advance the iterator
+        iter.next().unwrap();
+
+        let right = parse_not(iter)?.into_condition();
+        left = Operand::Condition(IRCondition::And(
+            Box::new(left.into_condition()),
+            Box::new(right),
+        ));
+    }
+
+    Ok(left)
+}
+
+fn parse_or<'a, I>(iter: &mut Peekable<I>) -> Result<Operand, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    let mut left = parse_and(iter)?;
+
+    while let Some(Token::Or) = iter.peek().map(|t| &t.token) {
+        // Advance the iterator
+        iter.next().unwrap();
+
+        let right = parse_and(iter)?.into_condition();
+        left = Operand::Condition(IRCondition::Or(
+            Box::new(left.into_condition()),
+            Box::new(right),
+        ));
+    }
+
+    Ok(left)
+}
+
+/// Parses a Condition, like the one of an If-Statement or a Loop,
+/// where '!' binds more tightly than '&&', which binds more tightly than '||'
+pub fn parse<'a, I>(iter: &mut Peekable<I>) -> Result<IRCondition, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    Ok(parse_or(iter)?.into_condition())
+}
+
+/// Parses either an Expression or a Condition and turns it into
+/// an Expression
+pub fn parse_value<'a, I>(iter: &mut Peekable<I>) -> Result<IRExpression, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    Ok(parse_or(iter)?.into_expression())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::frontend::{
+        ir::{IROperation, IRValue},
+        lexer::{with_default_spans, Comparisons},
+        Span,
+    };
+
+    fn variable(name: &str) -> IRExpression {
+        IRExpression::Variable(name.to_owned(), Span::default())
+    }
+
+    fn number(value: i64) -> IRExpression {
+        IRExpression::Value(IRValue::Number(value), Span::default())
+    }
+
+    #[test]
+    fn less_equal() {
+        let tokens = with_default_spans(vec![
+            Token::Identifier("x".to_owned()),
+            Token::Comparison(Comparisons::LessEqual),
+            Token::ValueNumber(5),
+            Token::ClosingParan,
+        ]);
+
+        let expected = IRCondition::Comparison(IRComparison::LessEqual(variable("x"), number(5)));
+
+        assert_eq!(Ok(expected), parse(&mut tokens.iter().peekable()));
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let tokens = with_default_spans(vec![
+            Token::Identifier("a".to_owned()),
+            Token::Comparison(Comparisons::GreaterThan),
+            Token::ValueNumber(0),
+            Token::Or,
+            Token::Not,
+            Token::OpenParan,
+            Token::Identifier("b".to_owned()),
+            Token::Comparison(Comparisons::Equal),
+            Token::ValueNumber(3),
+            Token::ClosingParan,
+            Token::And,
+            Token::Identifier("c".to_owned()),
+            Token::Comparison(Comparisons::LessThan),
+            Token::ValueNumber(1),
+            Token::ClosingParan,
+        ]);
+
+        let expected = IRCondition::Or(
+            Box::new(IRCondition::Comparison(IRComparison::GreaterThan(
+                variable("a"),
+                number(0),
+            ))),
+            Box::new(IRCondition::And(
+                Box::new(IRCondition::Not(Box::new(IRCondition::Comparison(
+                    IRComparison::Equals(variable("b"), number(3)),
+                )))),
+                Box::new(IRCondition::Comparison(IRComparison::LessThan(
+                    variable("c"),
+                    number(1),
+                ))),
+            )),
+        );
+
+        assert_eq!(Ok(expected), parse(&mut tokens.iter().peekable()));
+    }
+
+    #[test]
+    fn parenthesized_expression() {
+        let tokens = with_default_spans(vec![
+            Token::OpenParan,
+            Token::Identifier("a".to_owned()),
+            Token::Plus,
+            Token::ValueNumber(1),
+            Token::ClosingParan,
+            Token::Multiply,
+            Token::ValueNumber(2),
+            Token::Comparison(Comparisons::Equal),
+            Token::ValueNumber(4),
+            Token::ClosingParan,
+        ]);
+
+        let expected = IRCondition::Comparison(IRComparison::Equals(
+            IRExpression::Operation(
+                IROperation::Multiply,
+                vec![
+                    IRExpression::Operation(
+                        IROperation::Add,
+                        vec![variable("a"), number(1)],
+                        Span::default(),
+                    ),
+                    number(2),
+                ],
+                Span::default(),
+            ),
+            number(4),
+        ));
+
+        assert_eq!(Ok(expected), parse(&mut tokens.iter().peekable()));
+    }
+
+    #[test]
+    fn boolean_operands() {
+        let tokens = with_default_spans(vec![
+            Token::Not,
+            Token::Identifier("x".to_owned()),
+            Token::And,
+            Token::ValueBool(true),
+            Token::ClosingParan,
+        ]);
+
+        let expected = IRCondition::And(
+            Box::new(IRCondition::Not(Box::new(IRCondition::Expression(
+                variable("x"),
+            )))),
+            Box::new(IRCondition::Expression(IRExpression::Value(
+                IRValue::Bool(true),
+                Span::default(),
+            ))),
+        );
+
+        assert_eq!(Ok(expected), parse(&mut tokens.iter().peekable()));
+    }
+
+    #[test]
+    fn comparison_as_value() {
+        let tokens = with_default_spans(vec![
+            Token::Identifier("x".to_owned()),
+            Token::Comparison(Comparisons::GreaterThan),
+            Token::ValueNumber(3),
+            Token::Semicolon,
+        ]);
+
+        let expected = IRExpression::Condition(
+            Box::new(IRCondition::Comparison(IRComparison::GreaterThan(
+                variable("x"),
+                number(3),
+            ))),
+            Span::default(),
+        );
+
+        assert_eq!(Ok(expected), parse_value(&mut tokens.iter().peekable()));
+    }
+}
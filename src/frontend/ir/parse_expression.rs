@@ -1,185 +1,497 @@
 use super::*;
-use crate::frontend::lexer::Token;
+use crate::frontend::lexer::{SpannedToken, Token};
 use std::iter::Peekable;
 
-fn parse_single(token: &Token) -> Option<IRExpression> {
+/// The Operation and Precedence of a binary Operator, where a higher
+/// Precedence binds more tightly
+fn binary_operator(token: &Token) -> Option<(IROperation, u8)> {
     match token {
-        Token::ValueNumber(value) => Some(IRExpression::Value(IRValue::Number(*value))),
-        Token::Identifier(value) => Some(IRExpression::Variable(value.clone())),
+        Token::Plus => Some((IROperation::Add, 1)),
+        Token::Minus => Some((IROperation::Sub, 1)),
+        Token::Multiply => Some((IROperation::Multiply, 2)),
+        Token::Divide => Some((IROperation::Divide, 2)),
+        Token::Modulo => Some((IROperation::Modulo, 2)),
         _ => None,
     }
 }
 
-pub fn parse_expression<'a, I>(iter: &mut Peekable<I>) -> Option<IRExpression>
+/// Parses a single Operand, which is either a Value, a Variable,
+/// a Function- or Builtin-Call, a Negation or an Expression in Parentheses
+fn parse_primary<'a, I>(iter: &mut Peekable<I>) -> Result<IRExpression, CompileError>
 where
-    I: Iterator<Item = &'a Token>,
+    I: Iterator<Item = &'a SpannedToken>,
 {
-    let first = iter.next()?;
-    let second = iter.peek()?;
-
-    match second {
-        Token::Plus | Token::Minus | Token::Multiply => {
-            let op = match second {
-                Token::Plus => IROperation::Add,
-                Token::Minus => IROperation::Sub,
-                Token::Multiply => IROperation::Multiply,
-                _ => return None,
-            };
-
-            // Advance the iterator
-            iter.next().unwrap();
-
-            let first_part = match parse_single(first) {
-                Some(f) => f,
-                None => return None,
-            };
-
-            let other_part = match parse_expression(iter) {
-                Some(o) => o,
-                None => return None,
-            };
-
-            return Some(IRExpression::Operation(op, vec![first_part, other_part]));
-        }
-        Token::Comparison(_) => {
-            return parse_single(first);
+    let token = parse_util::next(iter)?;
+    match token.token {
+        // The Minimum is only produced for a Literal one above the Maximum,
+        // which is only valid directly after a Minus
+        Token::ValueNumber(i64::MIN) => Err(CompileError::lex(
+            format!("Number '{}' is too large", i64::MAX as u64 + 1),
+            token.span,
+        )),
+        Token::ValueNumber(value) => Ok(IRExpression::Value(IRValue::Number(value), token.span)),
+        Token::ValueBool(value) => Ok(IRExpression::Value(IRValue::Bool(value), token.span)),
+        // Characters are just another way to write their Code
+        Token::ValueChar(value) => Ok(IRExpression::Value(
+            IRValue::Number(value as i64),
+            token.span,
+        )),
+        Token::ValueString(ref value) => Ok(IRExpression::Value(
+            IRValue::String(value.clone()),
+            token.span,
+        )),
+        Token::Identifier(ref name) => match iter.peek().map(|t| &t.token) {
+            Some(Token::OpenParan) => {
+                let (inner, args_span) = parse_passed_args::parse(iter)?;
+
+                Ok(IRExpression::Call(
+                    name.clone(),
+                    inner,
+                    token.span.merge(&args_span),
+                ))
+            }
+            _ => Ok(IRExpression::Variable(name.clone(), token.span)),
+        },
+        // Builtins that return a Value, like 'read_int', are used the
+        // same as any other Function
+        Token::Builtin(ref builtin) => {
+            let (inner, args_span) = parse_passed_args::parse(iter)?;
+
+            Ok(IRExpression::Call(
+                builtin.name().to_owned(),
+                inner,
+                token.span.merge(&args_span),
+            ))
         }
-        Token::Semicolon | Token::ClosingParan | Token::Comma => {
-            return parse_single(first);
+        Token::Minus => {
+            if let Some(Token::ValueNumber(i64::MIN)) = iter.peek().map(|t| &t.token) {
+                let literal = iter.next().unwrap();
+                return Ok(IRExpression::Value(
+                    IRValue::Number(i64::MIN),
+                    token.span.merge(&literal.span),
+                ));
+            }
+
+            // The Negation binds more tightly than any binary Operator
+            let operand = parse_primary(iter)?;
+            let span = token.span.merge(&operand.span());
+
+            match operand {
+                IRExpression::Value(IRValue::Number(value), _) => Ok(IRExpression::Value(
+                    IRValue::Number(value.wrapping_neg()),
+                    span,
+                )),
+                _ => Ok(IRExpression::Operation(
+                    IROperation::Sub,
+                    vec![IRExpression::Value(IRValue::Number(0), token.span), operand],
+                    span,
+                )),
+            }
         }
         Token::OpenParan => {
-            let name = match first {
-                Token::Identifier(ref name) => name.clone(),
-                _ => return None,
-            };
+            let inner = parse_binary(iter, 0)?;
+            parse_util::expect(iter, &Token::ClosingParan)?;
 
-            let inner = parse_passed_args::parse(iter)?;
-
-            return Some(IRExpression::Call(name.to_owned(), inner));
+            Ok(inner)
         }
-        _ => {
-            log::error!("Unexpected: {:?}", second);
+        _ => Err(parse_util::unexpected(token, "expression")),
+    }
+}
+
+/// Parses an Expression using Precedence-Climbing, only consuming
+/// Operators with at least the given Precedence
+pub fn parse_binary<'a, I>(
+    iter: &mut Peekable<I>,
+    min_precedence: u8,
+) -> Result<IRExpression, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    let left = parse_primary(iter)?;
+    continue_binary(iter, left, min_precedence)
+}
+
+/// Continues parsing an Expression whose first Operand was
+/// already parsed
+pub fn continue_binary<'a, I>(
+    iter: &mut Peekable<I>,
+    mut left: IRExpression,
+    min_precedence: u8,
+) -> Result<IRExpression, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    while let Some((op, precedence)) = iter.peek().and_then(|t| binary_operator(&t.token)) {
+        if precedence < min_precedence {
+            break;
         }
-    };
 
-    None
+        // Advance the iterator
+        iter.next().unwrap();
+
+        // All Operators are left-associative, so the right Side only
+        // takes Operators that bind more tightly
+        let right = parse_binary(iter, precedence + 1)?;
+
+        let span = left.span().merge(&right.span());
+        left = IRExpression::Operation(op, vec![left, right], span);
+    }
+
+    Ok(left)
+}
+
+pub fn parse_expression<'a, I>(iter: &mut Peekable<I>) -> Result<IRExpression, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    let exp = parse_condition::parse_value(iter)?;
+
+    let next = parse_util::peek(iter)?;
+    match next.token {
+        Token::Semicolon | Token::ClosingParan | Token::Comma => Ok(exp),
+        _ => Err(parse_util::unexpected(
+            next,
+            "operator or end of expression",
+        )),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::frontend::lexer::{with_default_spans, BuiltIns};
 
     #[test]
     fn simple_value() {
-        let tokens = vec![Token::ValueNumber(1), Token::Semicolon];
-        let expected = IRExpression::Value(IRValue::Number(1));
+        let tokens = with_default_spans(vec![Token::ValueNumber(1), Token::Semicolon]);
+        let expected = IRExpression::Value(IRValue::Number(1), Span::default());
 
         assert_eq!(
-            Some(expected),
+            Ok(expected),
+            parse_expression(&mut tokens.iter().peekable())
+        );
+    }
+    #[test]
+    fn char_value() {
+        let tokens = with_default_spans(vec![Token::ValueChar('A'), Token::Semicolon]);
+        let expected = IRExpression::Value(IRValue::Number(65), Span::default());
+
+        assert_eq!(
+            Ok(expected),
             parse_expression(&mut tokens.iter().peekable())
         );
     }
     #[test]
     fn simple_variable() {
-        let tokens = vec![Token::Identifier("test".to_owned()), Token::Semicolon];
-        let expected = IRExpression::Variable("test".to_owned());
+        let tokens =
+            with_default_spans(vec![Token::Identifier("test".to_owned()), Token::Semicolon]);
+        let expected = IRExpression::Variable("test".to_owned(), Span::default());
 
         assert_eq!(
-            Some(expected),
+            Ok(expected),
             parse_expression(&mut tokens.iter().peekable())
         );
     }
     #[test]
     fn simple_expression() {
-        let tokens = vec![
+        let tokens = with_default_spans(vec![
             Token::ValueNumber(1),
             Token::Plus,
             Token::ValueNumber(1),
             Token::Semicolon,
-        ];
+        ]);
         let expected = IRExpression::Operation(
             IROperation::Add,
             vec![
-                IRExpression::Value(IRValue::Number(1)),
-                IRExpression::Value(IRValue::Number(1)),
+                IRExpression::Value(IRValue::Number(1), Span::default()),
+                IRExpression::Value(IRValue::Number(1), Span::default()),
             ],
+            Span::default(),
         );
 
         assert_eq!(
-            Some(expected),
+            Ok(expected),
             parse_expression(&mut tokens.iter().peekable())
         );
     }
 
     #[test]
     fn complex_expression() {
-        let tokens = vec![
+        let tokens = with_default_spans(vec![
             Token::ValueNumber(1),
             Token::Plus,
             Token::ValueNumber(1),
             Token::Plus,
             Token::ValueNumber(1),
             Token::Semicolon,
-        ];
+        ]);
         let expected = IRExpression::Operation(
             IROperation::Add,
             vec![
-                IRExpression::Value(IRValue::Number(1)),
                 IRExpression::Operation(
                     IROperation::Add,
                     vec![
-                        IRExpression::Value(IRValue::Number(1)),
-                        IRExpression::Value(IRValue::Number(1)),
+                        IRExpression::Value(IRValue::Number(1), Span::default()),
+                        IRExpression::Value(IRValue::Number(1), Span::default()),
+                    ],
+                    Span::default(),
+                ),
+                IRExpression::Value(IRValue::Number(1), Span::default()),
+            ],
+            Span::default(),
+        );
+
+        assert_eq!(
+            Ok(expected),
+            parse_expression(&mut tokens.iter().peekable())
+        );
+    }
+
+    #[test]
+    fn left_associative() {
+        let tokens = with_default_spans(vec![
+            Token::ValueNumber(3),
+            Token::Minus,
+            Token::ValueNumber(2),
+            Token::Minus,
+            Token::ValueNumber(1),
+            Token::Semicolon,
+        ]);
+        let expected = IRExpression::Operation(
+            IROperation::Sub,
+            vec![
+                IRExpression::Operation(
+                    IROperation::Sub,
+                    vec![
+                        IRExpression::Value(IRValue::Number(3), Span::default()),
+                        IRExpression::Value(IRValue::Number(2), Span::default()),
+                    ],
+                    Span::default(),
+                ),
+                IRExpression::Value(IRValue::Number(1), Span::default()),
+            ],
+            Span::default(),
+        );
+
+        assert_eq!(
+            Ok(expected),
+            parse_expression(&mut tokens.iter().peekable())
+        );
+    }
+
+    #[test]
+    fn precedence() {
+        let tokens = with_default_spans(vec![
+            Token::ValueNumber(1),
+            Token::Plus,
+            Token::ValueNumber(2),
+            Token::Multiply,
+            Token::ValueNumber(3),
+            Token::Semicolon,
+        ]);
+        let expected = IRExpression::Operation(
+            IROperation::Add,
+            vec![
+                IRExpression::Value(IRValue::Number(1), Span::default()),
+                IRExpression::Operation(
+                    IROperation::Multiply,
+                    vec![
+                        IRExpression::Value(IRValue::Number(2), Span::default()),
+                        IRExpression::Value(IRValue::Number(3), Span::default()),
                     ],
+                    Span::default(),
                 ),
             ],
+            Span::default(),
         );
 
         assert_eq!(
-            Some(expected),
+            Ok(expected),
+            parse_expression(&mut tokens.iter().peekable())
+        );
+    }
+
+    #[test]
+    fn parentheses() {
+        let tokens = with_default_spans(vec![
+            Token::OpenParan,
+            Token::ValueNumber(1),
+            Token::Plus,
+            Token::Identifier("test_func".to_owned()),
+            Token::OpenParan,
+            Token::ClosingParan,
+            Token::ClosingParan,
+            Token::Multiply,
+            Token::ValueNumber(3),
+            Token::Semicolon,
+        ]);
+        let expected = IRExpression::Operation(
+            IROperation::Multiply,
+            vec![
+                IRExpression::Operation(
+                    IROperation::Add,
+                    vec![
+                        IRExpression::Value(IRValue::Number(1), Span::default()),
+                        IRExpression::Call("test_func".to_owned(), vec![], Span::default()),
+                    ],
+                    Span::default(),
+                ),
+                IRExpression::Value(IRValue::Number(3), Span::default()),
+            ],
+            Span::default(),
+        );
+
+        assert_eq!(
+            Ok(expected),
+            parse_expression(&mut tokens.iter().peekable())
+        );
+    }
+
+    #[test]
+    fn missing_closing_paran() {
+        let tokens = with_default_spans(vec![
+            Token::OpenParan,
+            Token::ValueNumber(1),
+            Token::Semicolon,
+        ]);
+
+        assert_eq!(
+            Err(CompileError::parse(
+                "Expected ')', found ';'",
+                Span::default()
+            )),
+            parse_expression(&mut tokens.iter().peekable())
+        );
+    }
+
+    #[test]
+    fn negation() {
+        let tokens = with_default_spans(vec![
+            Token::Minus,
+            Token::ValueNumber(2),
+            Token::Multiply,
+            Token::Minus,
+            Token::Identifier("x".to_owned()),
+            Token::Semicolon,
+        ]);
+        let expected = IRExpression::Operation(
+            IROperation::Multiply,
+            vec![
+                IRExpression::Value(IRValue::Number(-2), Span::default()),
+                IRExpression::Operation(
+                    IROperation::Sub,
+                    vec![
+                        IRExpression::Value(IRValue::Number(0), Span::default()),
+                        IRExpression::Variable("x".to_owned(), Span::default()),
+                    ],
+                    Span::default(),
+                ),
+            ],
+            Span::default(),
+        );
+
+        assert_eq!(
+            Ok(expected),
+            parse_expression(&mut tokens.iter().peekable())
+        );
+    }
+
+    #[test]
+    fn minimum_literal() {
+        let tokens = with_default_spans(vec![
+            Token::Minus,
+            Token::ValueNumber(i64::MIN),
+            Token::Minus,
+            Token::ValueNumber(i64::MIN),
+            Token::Semicolon,
+        ]);
+
+        assert_eq!(
+            Err(CompileError::lex(
+                "Number '9223372036854775808' is too large",
+                Span::default()
+            )),
+            parse_expression(&mut tokens.iter().peekable())
+        );
+
+        let tokens = with_default_spans(vec![
+            Token::Minus,
+            Token::ValueNumber(i64::MIN),
+            Token::Semicolon,
+        ]);
+        let expected = IRExpression::Value(IRValue::Number(i64::MIN), Span::default());
+
+        assert_eq!(
+            Ok(expected),
             parse_expression(&mut tokens.iter().peekable())
         );
     }
 
     #[test]
     fn parse_call_no_param() {
-        let tokens = vec![
+        let tokens = with_default_spans(vec![
             Token::Identifier("test_func".to_owned()),
             Token::OpenParan,
             Token::ClosingParan,
             Token::Semicolon,
-        ];
+        ]);
+
+        let expected = IRExpression::Call("test_func".to_owned(), vec![], Span::default());
+
+        assert_eq!(
+            Ok(expected),
+            parse_expression(&mut tokens.iter().peekable())
+        );
+    }
+    #[test]
+    fn parse_builtin_call() {
+        let tokens = with_default_spans(vec![
+            Token::Builtin(BuiltIns::ReadInt),
+            Token::OpenParan,
+            Token::ClosingParan,
+            Token::Plus,
+            Token::ValueNumber(1),
+            Token::Semicolon,
+        ]);
 
-        let expected = IRExpression::Call("test_func".to_owned(), vec![]);
+        let expected = IRExpression::Operation(
+            IROperation::Add,
+            vec![
+                IRExpression::Call("read_int".to_owned(), vec![], Span::default()),
+                IRExpression::Value(IRValue::Number(1), Span::default()),
+            ],
+            Span::default(),
+        );
 
         assert_eq!(
-            Some(expected),
+            Ok(expected),
             parse_expression(&mut tokens.iter().peekable())
         );
     }
     #[test]
     fn parse_call_one_param() {
-        let tokens = vec![
+        let tokens = with_default_spans(vec![
             Token::Identifier("test_func".to_owned()),
             Token::OpenParan,
             Token::ValueNumber(1),
             Token::ClosingParan,
             Token::Semicolon,
-        ];
+        ]);
 
         let expected = IRExpression::Call(
             "test_func".to_owned(),
-            vec![IRExpression::Value(IRValue::Number(1))],
+            vec![IRExpression::Value(IRValue::Number(1), Span::default())],
+            Span::default(),
         );
 
         assert_eq!(
-            Some(expected),
+            Ok(expected),
             parse_expression(&mut tokens.iter().peekable())
         );
     }
     #[test]
     fn parse_call_two_params() {
-        let tokens = vec![
+        let tokens = with_default_spans(vec![
             Token::Identifier("test_func".to_owned()),
             Token::OpenParan,
             Token::ValueNumber(1),
@@ -187,18 +499,19 @@ mod tests {
             Token::ValueNumber(2),
             Token::ClosingParan,
             Token::Semicolon,
-        ];
+        ]);
 
         let expected = IRExpression::Call(
             "test_func".to_owned(),
             vec![
-                IRExpression::Value(IRValue::Number(1)),
-                IRExpression::Value(IRValue::Number(2)),
+                IRExpression::Value(IRValue::Number(1), Span::default()),
+                IRExpression::Value(IRValue::Number(2), Span::default()),
             ],
+            Span::default(),
         );
 
         assert_eq!(
-            Some(expected),
+            Ok(expected),
             parse_expression(&mut tokens.iter().peekable())
         );
     }
@@ -1,7 +1,10 @@
 use std::iter::Peekable;
 
-use super::{IRExpression, IRNode};
-use crate::frontend::lexer::Token;
+use super::{parse_expression, parse_util, IRNode};
+use crate::{
+    error::CompileError,
+    frontend::lexer::{SpannedToken, Token},
+};
 
 mod parse_builtin;
 mod parse_identifier;
@@ -9,98 +12,110 @@ mod parse_if;
 mod parse_primitive;
 mod parse_while;
 
-pub fn inner_parse<'a, I>(iter: &mut Peekable<I>) -> Option<Vec<Vec<IRNode>>>
+pub fn inner_parse<'a, I>(iter: &mut Peekable<I>) -> Result<Vec<Vec<IRNode>>, CompileError>
 where
-    I: Iterator<Item = &'a Token>,
+    I: Iterator<Item = &'a SpannedToken>,
 {
     let mut result = Vec::new();
 
     let mut current_statement = Vec::with_capacity(3);
     while let Some(token) = iter.next() {
-        match token {
+        // A Statement has to be closed with a ';' before anything else follows
+        if !current_statement.is_empty() && token.token != Token::Semicolon {
+            return Err(parse_util::unexpected(token, "';'"));
+        }
+
+        match token.token {
             Token::Primitive(ref prim) => {
-                let parsed = parse_primitive::parse(prim, iter)?;
+                let parsed = parse_primitive::parse(prim, token.span, iter)?;
                 current_statement.push(parsed);
             }
             Token::Identifier(ref name) => {
-                let parsed = parse_identifier::parse(name, iter)?;
+                let parsed = parse_identifier::parse(name, token.span, iter)?;
                 current_statement.push(parsed);
             }
             Token::Builtin(ref builtin) => {
-                let parsed = parse_builtin::parse(builtin, iter)?;
-                current_statement.push(IRNode::SingleExpression(parsed));
+                let parsed = parse_builtin::parse(builtin, token.span, iter)?;
+                let span = parsed.span();
+                current_statement.push(IRNode::SingleExpression(parsed, span));
             }
             Token::Semicolon => {
                 result.push(current_statement.clone());
                 current_statement.clear();
             }
             Token::If => {
-                let parsed = parse_if::parse(iter)?;
+                let parsed = parse_if::parse(token.span, iter)?;
                 current_statement.push(parsed);
 
                 result.push(current_statement.clone());
                 current_statement.clear();
             }
             Token::While => {
-                let parsed = parse_while::parse(iter)?;
+                let parsed = parse_while::parse(token.span, iter)?;
                 current_statement.push(parsed);
 
                 result.push(current_statement.clone());
                 current_statement.clear();
             }
             Token::Return => {
-                let ret_exp = match iter.peek() {
-                    Some(Token::Identifier(ref name)) => {
-                        Some(IRExpression::Variable(name.to_owned()))
-                    }
-                    Some(Token::Semicolon) => None,
-                    _ => return None,
+                let ret_exp = match parse_util::peek(iter)?.token {
+                    Token::Semicolon => None,
+                    _ => Some(parse_expression(iter)?),
                 };
 
-                if ret_exp.is_some() {
-                    iter.next().unwrap();
-                }
-
-                current_statement.push(IRNode::Return(ret_exp));
-            }
-            Token::ClosingCurly => return Some(result),
-            _ => {
-                log::error!("Unknown: {:?}", token);
+                let span = match ret_exp {
+                    Some(ref exp) => token.span.merge(&exp.span()),
+                    None => token.span,
+                };
+                current_statement.push(IRNode::Return(ret_exp, span));
             }
+            Token::ClosingCurly => return Ok(result),
+            Token::EndOfFile => return Err(parse_util::unexpected(token, "'}'")),
+            _ => return Err(parse_util::unexpected(token, "statement")),
         };
     }
 
-    Some(result)
+    if let Some(node) = current_statement.last() {
+        return Err(CompileError::parse(
+            "Expected ';', found end of input",
+            node.span(),
+        ));
+    }
+
+    Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::frontend::ir::{IRType, IRValue};
-    use crate::frontend::lexer::Primitives;
+    use crate::frontend::ir::{IRExpression, IROperation, IRType, IRValue};
+    use crate::frontend::lexer::{with_default_spans, BuiltIns, Primitives};
+    use crate::frontend::Span;
 
     #[test]
     fn example_1() {
-        let tokens = vec![
+        let tokens = with_default_spans(vec![
             Token::Primitive(Primitives::Number),
             Token::Identifier("test".to_owned()),
             Token::Assignment,
             Token::ValueNumber(12),
             Token::Semicolon,
-        ];
+        ]);
 
-        let expected = vec![vec![
-            IRNode::DeclareVariable("test".to_owned(), IRType::Number),
-            IRNode::Assignment("test".to_owned(), IRExpression::Value(IRValue::Number(12))),
-        ]];
+        let expected = vec![vec![IRNode::DeclareVariable(
+            "test".to_owned(),
+            IRType::I64,
+            Some(IRExpression::Value(IRValue::Number(12), Span::default())),
+            Span::default(),
+        )]];
 
-        assert_eq!(Some(expected), inner_parse(&mut tokens.iter().peekable()));
+        assert_eq!(Ok(expected), inner_parse(&mut tokens.iter().peekable()));
     }
 
     #[test]
     fn example_2() {
-        let tokens = vec![
+        let tokens = with_default_spans(vec![
             Token::Identifier("test".to_owned()),
             Token::OpenParan,
             Token::ValueNumber(1),
@@ -108,16 +123,96 @@ mod tests {
             Token::ValueNumber(5),
             Token::ClosingParan,
             Token::Semicolon,
+        ]);
+
+        let expected = vec![vec![IRNode::SingleExpression(
+            IRExpression::Call(
+                "test".to_owned(),
+                vec![
+                    IRExpression::Value(IRValue::Number(1), Span::default()),
+                    IRExpression::Value(IRValue::Number(5), Span::default()),
+                ],
+                Span::default(),
+            ),
+            Span::default(),
+        )]];
+
+        assert_eq!(Ok(expected), inner_parse(&mut tokens.iter().peekable()));
+    }
+
+    #[test]
+    fn return_expression() {
+        let tokens = with_default_spans(vec![
+            Token::Return,
+            Token::Identifier("n1".to_owned()),
+            Token::Plus,
+            Token::ValueNumber(5),
+            Token::Semicolon,
+            Token::Return,
+            Token::Semicolon,
+        ]);
+
+        let expected = vec![
+            vec![IRNode::Return(
+                Some(IRExpression::Operation(
+                    IROperation::Add,
+                    vec![
+                        IRExpression::Variable("n1".to_owned(), Span::default()),
+                        IRExpression::Value(IRValue::Number(5), Span::default()),
+                    ],
+                    Span::default(),
+                )),
+                Span::default(),
+            )],
+            vec![IRNode::Return(None, Span::default())],
         ];
 
-        let expected = vec![vec![IRNode::SingleExpression(IRExpression::Call(
-            "test".to_owned(),
-            vec![
-                IRExpression::Value(IRValue::Number(1)),
-                IRExpression::Value(IRValue::Number(5)),
-            ],
-        ))]];
+        assert_eq!(Ok(expected), inner_parse(&mut tokens.iter().peekable()));
+    }
 
-        assert_eq!(Some(expected), inner_parse(&mut tokens.iter().peekable()));
+    #[test]
+    fn missing_semicolon() {
+        let tokens = with_default_spans(vec![
+            Token::Builtin(BuiltIns::Print),
+            Token::OpenParan,
+            Token::Identifier("x".to_owned()),
+            Token::ClosingParan,
+            Token::ClosingCurly,
+        ]);
+        assert_eq!(
+            Err(CompileError::parse(
+                "Expected ';', found '}'",
+                Span::default()
+            )),
+            inner_parse(&mut tokens.iter().peekable())
+        );
+
+        let tokens = with_default_spans(vec![
+            Token::Builtin(BuiltIns::PrintInt),
+            Token::OpenParan,
+            Token::ValueNumber(9),
+            Token::ClosingParan,
+            Token::Builtin(BuiltIns::PrintInt),
+        ]);
+        assert_eq!(
+            Err(CompileError::parse(
+                "Expected ';', found builtin 'print_int'",
+                Span::default()
+            )),
+            inner_parse(&mut tokens.iter().peekable())
+        );
+
+        let tokens = with_default_spans(vec![
+            Token::Identifier("x".to_owned()),
+            Token::OpenParan,
+            Token::ClosingParan,
+        ]);
+        assert_eq!(
+            Err(CompileError::parse(
+                "Expected ';', found end of input",
+                Span::default()
+            )),
+            inner_parse(&mut tokens.iter().peekable())
+        );
     }
 }
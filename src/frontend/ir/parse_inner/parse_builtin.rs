@@ -1,32 +1,27 @@
 use std::iter::Peekable;
 
-use crate::frontend::{
-    ir::{parse_expression::parse_expression, IRExpression},
-    lexer::{BuiltIns, Token},
+use crate::{
+    error::CompileError,
+    frontend::{
+        ir::{parse_passed_args, IRExpression},
+        lexer::{BuiltIns, SpannedToken},
+        Span,
+    },
 };
 
-pub fn parse<'a, I>(builtin: &BuiltIns, iter: &mut Peekable<I>) -> Option<IRExpression>
+pub fn parse<'a, I>(
+    builtin: &BuiltIns,
+    builtin_span: Span,
+    iter: &mut Peekable<I>,
+) -> Result<IRExpression, CompileError>
 where
-    I: Iterator<Item = &'a Token>,
+    I: Iterator<Item = &'a SpannedToken>,
 {
-    let next_token = iter.peek()?;
+    let (inner, args_span) = parse_passed_args::parse(iter)?;
 
-    match next_token {
-        Token::OpenParan => {
-            iter.next().unwrap();
-
-            let inner = parse_expression(iter)?;
-            iter.next().unwrap();
-
-            let func_name = match builtin {
-                BuiltIns::Print => "print".to_owned(),
-            };
-
-            Some(IRExpression::Call(func_name, vec![inner]))
-        }
-        _ => {
-            log::error!("Unknown operation for identifier: {:?}", next_token);
-            None
-        }
-    }
+    Ok(IRExpression::Call(
+        builtin.name().to_owned(),
+        inner,
+        builtin_span.merge(&args_span),
+    ))
 }
@@ -1,47 +1,45 @@
 use std::iter::Peekable;
 
-use crate::frontend::{
-    ir::{parse_expression, parse_passed_args, IRExpression, IRNode},
-    lexer::Token,
+use crate::{
+    error::CompileError,
+    frontend::{
+        ir::{parse_expression, parse_passed_args, parse_util, IRExpression, IRNode},
+        lexer::{SpannedToken, Token},
+        Span,
+    },
 };
 
-pub fn parse<'a, I>(name: &str, iter: &mut Peekable<I>) -> Option<IRNode>
+pub fn parse<'a, I>(
+    name: &str,
+    name_span: Span,
+    iter: &mut Peekable<I>,
+) -> Result<IRNode, CompileError>
 where
-    I: Iterator<Item = &'a Token>,
+    I: Iterator<Item = &'a SpannedToken>,
 {
-    let next_token = match iter.peek() {
-        Some(n) => n,
-        None => return None,
-    };
+    let next_token = parse_util::peek(iter)?;
 
-    match next_token {
+    match next_token.token {
         Token::Assignment => {
             // Advance the iterator
             iter.next().unwrap();
 
-            match parse_expression(iter) {
-                Some(exp) => Some(IRNode::Assignment(name.to_owned(), exp)),
-                _ => {
-                    log::error!("Parsing Expression");
-                    None
-                }
-            }
+            let exp = parse_expression(iter)?;
+            let span = name_span.merge(&exp.span());
+            Ok(IRNode::Assignment(name.to_owned(), exp, span))
         }
         Token::OpenParan => {
-            let inner = parse_passed_args::parse(iter)?;
+            let (inner, args_span) = parse_passed_args::parse(iter)?;
 
-            Some(IRNode::SingleExpression(IRExpression::Call(
-                name.to_owned(),
-                inner,
-            )))
-        }
-        _ => {
-            log::error!(
-                "Unknown operation for identifier('{}'): {:?}",
-                name,
-                next_token
-            );
-            None
+            let span = name_span.merge(&args_span);
+            Ok(IRNode::SingleExpression(
+                IRExpression::Call(name.to_owned(), inner, span),
+                span,
+            ))
         }
+        _ => Err(parse_util::unexpected(
+            next_token,
+            &format!("'=' or '(' after '{}'", name),
+        )),
     }
 }
@@ -1,55 +1,145 @@
 use std::iter::Peekable;
 
-use crate::frontend::{
-    ir::{parse_expression, IRComparison, IRNode},
-    lexer::{Comparisons, Token},
+use crate::{
+    error::CompileError,
+    frontend::{
+        ir::{parse_condition, parse_util, IRNode},
+        lexer::{SpannedToken, Token},
+        Span,
+    },
 };
 
 use super::inner_parse;
 
-pub fn parse<'a, I>(iter: &mut Peekable<I>) -> Option<IRNode>
+pub fn parse<'a, I>(if_span: Span, iter: &mut Peekable<I>) -> Result<IRNode, CompileError>
 where
-    I: Iterator<Item = &'a Token>,
+    I: Iterator<Item = &'a SpannedToken>,
 {
-    match iter.peek().unwrap() {
-        Token::OpenParan => {
-            iter.next().unwrap();
-        }
-        _ => {
-            log::error!("Unknown-Peek: {:?}", iter.peek());
-            return None;
-        }
-    };
+    parse_util::expect(iter, &Token::OpenParan)?;
+
+    let condition = parse_condition::parse(iter)?;
 
-    let first_part = parse_expression(iter)?;
+    let closing = parse_util::expect(iter, &Token::ClosingParan)?;
 
-    let comparison_token = iter.next().unwrap();
+    parse_util::expect(iter, &Token::OpenCurly)?;
 
-    let second_part = parse_expression(iter)?;
+    let inner_scope = inner_parse(iter)?;
 
-    let comp = match comparison_token {
-        Token::Comparison(comp) => match comp {
-            Comparisons::Equal => IRComparison::Equals(first_part, second_part),
-            Comparisons::GreaterThan => {
-                unimplemented!("Does not yet support comparisons other than an equality check");
+    let else_scope = match iter.peek().map(|t| &t.token) {
+        Some(Token::Else) => {
+            // Advance the iterator
+            iter.next().unwrap();
+
+            let next = parse_util::next(iter)?;
+            match next.token {
+                // An 'else if' is an else-Block that only contains the next If
+                Token::If => Some(vec![vec![parse(next.span, iter)?]]),
+                Token::OpenCurly => Some(inner_parse(iter)?),
+                _ => return Err(parse_util::unexpected(next, "'if' or '{'")),
             }
-        },
-        _ => return None,
+        }
+        _ => None,
     };
 
-    match iter.next().unwrap() {
-        Token::ClosingParan => {}
-        _ => return None,
-    };
+    Ok(IRNode::Conditional(
+        condition,
+        inner_scope,
+        else_scope,
+        if_span.merge(&closing.span),
+    ))
+}
 
-    match iter.peek().unwrap() {
-        Token::OpenCurly => {
-            iter.next().unwrap();
-        }
-        _ => return None,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::frontend::{
+        ir::{IRComparison, IRCondition, IRExpression, IRValue},
+        lexer::{with_default_spans, BuiltIns, Comparisons},
     };
 
-    let inner_scope = inner_parse(iter)?;
+    fn print_tokens(value: i64) -> Vec<Token> {
+        vec![
+            Token::OpenCurly,
+            Token::Builtin(BuiltIns::Print),
+            Token::OpenParan,
+            Token::ValueNumber(value),
+            Token::ClosingParan,
+            Token::Semicolon,
+            Token::ClosingCurly,
+        ]
+    }
+
+    fn print_node(value: i64) -> Vec<Vec<IRNode>> {
+        vec![vec![IRNode::SingleExpression(
+            IRExpression::Call(
+                "print".to_owned(),
+                vec![IRExpression::Value(IRValue::Number(value), Span::default())],
+                Span::default(),
+            ),
+            Span::default(),
+        )]]
+    }
+
+    fn condition_tokens(value: i64) -> Vec<Token> {
+        vec![
+            Token::OpenParan,
+            Token::Identifier("x".to_owned()),
+            Token::Comparison(Comparisons::Equal),
+            Token::ValueNumber(value),
+            Token::ClosingParan,
+        ]
+    }
+
+    fn condition(value: i64) -> IRCondition {
+        IRCondition::Comparison(IRComparison::Equals(
+            IRExpression::Variable("x".to_owned(), Span::default()),
+            IRExpression::Value(IRValue::Number(value), Span::default()),
+        ))
+    }
+
+    #[test]
+    fn else_if_chain() {
+        let mut raw_tokens = condition_tokens(1);
+        raw_tokens.extend(print_tokens(1));
+        raw_tokens.extend(vec![Token::Else, Token::If]);
+        raw_tokens.extend(condition_tokens(2));
+        raw_tokens.extend(print_tokens(2));
+        raw_tokens.push(Token::Else);
+        raw_tokens.extend(print_tokens(3));
+        let tokens = with_default_spans(raw_tokens);
+
+        let expected = IRNode::Conditional(
+            condition(1),
+            print_node(1),
+            Some(vec![vec![IRNode::Conditional(
+                condition(2),
+                print_node(2),
+                Some(print_node(3)),
+                Span::default(),
+            )]]),
+            Span::default(),
+        );
+
+        assert_eq!(
+            Ok(expected),
+            parse(Span::default(), &mut tokens.iter().peekable())
+        );
+    }
+
+    #[test]
+    fn else_without_block() {
+        let mut raw_tokens = condition_tokens(1);
+        raw_tokens.extend(print_tokens(1));
+        raw_tokens.extend(vec![Token::Else, Token::Semicolon]);
+        let tokens = with_default_spans(raw_tokens);
 
-    Some(IRNode::Conditional(comp, inner_scope))
+        assert_eq!(
+            Err(CompileError::parse(
+                "Expected 'if' or '{', found ';'",
+                Span::default()
+            )),
+            parse(Span::default(), &mut tokens.iter().peekable())
+        );
+    }
 }
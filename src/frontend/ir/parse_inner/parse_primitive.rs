@@ -1,25 +1,115 @@
 use std::iter::Peekable;
 
-use crate::frontend::{
-    ir::{IRNode, IRType},
-    lexer::{Primitives, Token},
+use crate::{
+    error::CompileError,
+    frontend::{
+        ir::{parse_expression, parse_util, IRNode, IRType},
+        lexer::{Primitives, SpannedToken, Token},
+        Span,
+    },
 };
 
-pub fn parse<'a, I>(prim: &Primitives, iter: &mut Peekable<I>) -> Option<IRNode>
+/// Parses a Declaration like 'number x;' or 'number x = 5;'
+pub fn parse<'a, I>(
+    prim: &Primitives,
+    prim_span: Span,
+    iter: &mut Peekable<I>,
+) -> Result<IRNode, CompileError>
 where
-    I: Iterator<Item = &'a Token>,
+    I: Iterator<Item = &'a SpannedToken>,
 {
-    let ir_type = match prim {
-        Primitives::Number => IRType::Number,
+    let ir_type = IRType::from(prim);
+
+    let name_token = parse_util::next(iter)?;
+    let name = match name_token.token {
+        Token::Identifier(ref name) => name,
+        _ => return Err(parse_util::unexpected(name_token, "variable name")),
     };
 
-    let next_token = iter.peek()?;
+    let next_token = parse_util::peek(iter)?;
+    match next_token.token {
+        Token::Assignment => {
+            // Advance the iterator
+            iter.next().unwrap();
 
-    match next_token {
-        Token::Identifier(ref name) => Some(IRNode::DeclareVariable(name.clone(), ir_type)),
-        _ => {
-            log::error!("Expected Identifier");
-            None
+            let exp = parse_expression(iter)?;
+            let span = prim_span.merge(&exp.span());
+            Ok(IRNode::DeclareVariable(
+                name.clone(),
+                ir_type,
+                Some(exp),
+                span,
+            ))
         }
+        // The Semicolon itself ends the Statement
+        Token::Semicolon => Ok(IRNode::DeclareVariable(
+            name.clone(),
+            ir_type,
+            None,
+            prim_span.merge(&name_token.span),
+        )),
+        _ => Err(parse_util::unexpected(
+            next_token,
+            &format!("'=' or ';' after '{}'", name),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::frontend::lexer::with_default_spans;
+
+    #[test]
+    fn without_initializer() {
+        let tokens = with_default_spans(vec![Token::Identifier("x".to_owned()), Token::Semicolon]);
+
+        assert_eq!(
+            Ok(IRNode::DeclareVariable(
+                "x".to_owned(),
+                IRType::U8,
+                None,
+                Span::default()
+            )),
+            parse(
+                &Primitives::U8,
+                Span::default(),
+                &mut tokens.iter().peekable()
+            )
+        );
+    }
+
+    #[test]
+    fn invalid_declarations() {
+        let missing_name = with_default_spans(vec![Token::ValueNumber(5), Token::Semicolon]);
+        let double_name = with_default_spans(vec![
+            Token::Identifier("x".to_owned()),
+            Token::Identifier("x".to_owned()),
+            Token::Semicolon,
+        ]);
+
+        assert_eq!(
+            Err(CompileError::parse(
+                "Expected variable name, found number '5'",
+                Span::default()
+            )),
+            parse(
+                &Primitives::Number,
+                Span::default(),
+                &mut missing_name.iter().peekable()
+            )
+        );
+        assert_eq!(
+            Err(CompileError::parse(
+                "Expected '=' or ';' after 'x', found identifier 'x'",
+                Span::default()
+            )),
+            parse(
+                &Primitives::Number,
+                Span::default(),
+                &mut double_name.iter().peekable()
+            )
+        );
     }
 }
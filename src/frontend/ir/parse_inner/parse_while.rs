@@ -1,55 +1,32 @@
 use std::iter::Peekable;
 
 use super::inner_parse;
-use crate::frontend::{
-    ir::{parse_expression, IRComparison, IRNode},
-    lexer::{Comparisons, Token},
+use crate::{
+    error::CompileError,
+    frontend::{
+        ir::{parse_condition, parse_util, IRNode},
+        lexer::{SpannedToken, Token},
+        Span,
+    },
 };
 
-pub fn parse<'a, I>(iter: &mut Peekable<I>) -> Option<IRNode>
+pub fn parse<'a, I>(while_span: Span, iter: &mut Peekable<I>) -> Result<IRNode, CompileError>
 where
-    I: Iterator<Item = &'a Token>,
+    I: Iterator<Item = &'a SpannedToken>,
 {
-    match iter.peek() {
-        Some(Token::OpenParan) => {
-            iter.next().unwrap();
-        }
-        _ => {
-            log::error!("Unexpected: {:?}", iter.peek());
-            return None;
-        }
-    };
+    parse_util::expect(iter, &Token::OpenParan)?;
 
-    let first_part = parse_expression(iter)?;
+    let condition = parse_condition::parse(iter)?;
 
-    let comparison_token = iter.next().unwrap();
+    let closing = parse_util::expect(iter, &Token::ClosingParan)?;
 
-    let second_part = parse_expression(iter)?;
-
-    let comp = match comparison_token {
-        Token::Comparison(cmp) => match cmp {
-            Comparisons::Equal => IRComparison::Equals(first_part, second_part),
-            Comparisons::GreaterThan => IRComparison::GreaterThan(first_part, second_part),
-        },
-        _ => {
-            log::error!("Expected Comparison: {:?}", comparison_token);
-            return None;
-        }
-    };
-
-    match iter.next().unwrap() {
-        Token::ClosingParan => {}
-        _ => return None,
-    };
-
-    match iter.peek().unwrap() {
-        Token::OpenCurly => {
-            iter.next().unwrap();
-        }
-        _ => return None,
-    };
+    parse_util::expect(iter, &Token::OpenCurly)?;
 
     let inner_scope = inner_parse(iter)?;
 
-    Some(IRNode::Loop(comp, inner_scope))
+    Ok(IRNode::Loop(
+        condition,
+        inner_scope,
+        while_span.merge(&closing.span),
+    ))
 }
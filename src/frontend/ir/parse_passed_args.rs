@@ -1,40 +1,106 @@
 use std::iter::Peekable;
 
-use crate::frontend::lexer::Token;
+use crate::{
+    error::CompileError,
+    frontend::{
+        lexer::{SpannedToken, Token},
+        Span,
+    },
+};
 
-use super::{IRExpression, IRValue};
+use super::{parse_expression, parse_util, IRExpression};
 
-pub fn parse<'a, I>(iter: &mut Peekable<I>) -> Option<Vec<IRExpression>>
+/// Parses the Arguments passed to a Function-Call, including the
+/// surrounding Parentheses, and returns them together with the
+/// Span of the entire Argument-List
+pub fn parse<'a, I>(iter: &mut Peekable<I>) -> Result<(Vec<IRExpression>, Span), CompileError>
 where
-    I: Iterator<Item = &'a Token>,
+    I: Iterator<Item = &'a SpannedToken>,
 {
     let mut result = Vec::new();
 
-    match iter.peek() {
-        Some(Token::OpenParan) => {}
-        _ => return None,
-    };
-    iter.next().unwrap();
+    let start_span = parse_util::expect(iter, &Token::OpenParan)?.span;
+
+    if let Some(Token::ClosingParan) = iter.peek().map(|t| &t.token) {
+        let closing = iter.next().unwrap();
+        return Ok((result, start_span.merge(&closing.span)));
+    }
 
-    while let Some(peeked) = iter.peek() {
-        match peeked {
+    loop {
+        result.push(parse_expression(iter)?);
+
+        let next = parse_util::next(iter)?;
+        match next.token {
             Token::ClosingParan => {
-                iter.next().unwrap();
-                break;
-            }
-            Token::ValueNumber(value) => {
-                iter.next().unwrap();
-                result.push(IRExpression::Value(IRValue::Number(*value)));
-            }
-            Token::Comma => {
-                iter.next().unwrap();
-            }
-            _ => {
-                log::error!("Unexpected: {:?}", peeked);
-                unimplemented!("Calling custom functions with params not supported yet");
+                return Ok((result, start_span.merge(&next.span)));
             }
+            Token::Comma => {}
+            _ => return Err(parse_util::unexpected(next, "',' or ')'")),
         };
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Some(result)
+    use crate::frontend::{
+        ir::{IROperation, IRValue},
+        lexer::with_default_spans,
+    };
+
+    #[test]
+    fn expressions_and_calls() {
+        let tokens = with_default_spans(vec![
+            Token::OpenParan,
+            Token::Identifier("a".to_owned()),
+            Token::Plus,
+            Token::ValueNumber(1),
+            Token::Comma,
+            Token::Identifier("bar".to_owned()),
+            Token::OpenParan,
+            Token::Identifier("b".to_owned()),
+            Token::ClosingParan,
+            Token::ClosingParan,
+        ]);
+
+        let expected = vec![
+            IRExpression::Operation(
+                IROperation::Add,
+                vec![
+                    IRExpression::Variable("a".to_owned(), Span::default()),
+                    IRExpression::Value(IRValue::Number(1), Span::default()),
+                ],
+                Span::default(),
+            ),
+            IRExpression::Call(
+                "bar".to_owned(),
+                vec![IRExpression::Variable("b".to_owned(), Span::default())],
+                Span::default(),
+            ),
+        ];
+
+        assert_eq!(
+            Ok((expected, Span::default())),
+            parse(&mut tokens.iter().peekable())
+        );
+    }
+
+    #[test]
+    fn missing_comma() {
+        let tokens = with_default_spans(vec![
+            Token::OpenParan,
+            Token::Identifier("a".to_owned()),
+            Token::Identifier("b".to_owned()),
+            Token::ClosingParan,
+        ]);
+
+        assert_eq!(
+            Err(CompileError::parse(
+                "Expected operator or end of expression, found identifier 'b'",
+                Span::default()
+            )),
+            parse(&mut tokens.iter().peekable())
+        );
+    }
 }
@@ -0,0 +1,55 @@
+use std::iter::Peekable;
+
+use crate::{
+    error::CompileError,
+    frontend::{
+        lexer::{SpannedToken, Token},
+        Span,
+    },
+};
+
+/// Creates the Error for a Token that was not expected at its Position
+pub fn unexpected(found: &SpannedToken, expected: &str) -> CompileError {
+    CompileError::parse(
+        format!("Expected {}, found {}", expected, found.token),
+        found.span,
+    )
+}
+
+fn end_of_input() -> CompileError {
+    CompileError::parse("Unexpected end of input", Span::default())
+}
+
+/// Consumes and returns the next Token
+pub fn next<'a, I>(iter: &mut Peekable<I>) -> Result<&'a SpannedToken, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    iter.next().ok_or_else(end_of_input)
+}
+
+/// Returns the next Token without consuming it
+pub fn peek<'a, I>(iter: &mut Peekable<I>) -> Result<&'a SpannedToken, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    iter.peek().copied().ok_or_else(end_of_input)
+}
+
+/// Consumes the next Token, if it is the expected one, and otherwise
+/// returns an Error
+pub fn expect<'a, I>(
+    iter: &mut Peekable<I>,
+    expected: &Token,
+) -> Result<&'a SpannedToken, CompileError>
+where
+    I: Iterator<Item = &'a SpannedToken>,
+{
+    let token = peek(iter)?;
+    if &token.token != expected {
+        return Err(unexpected(token, &expected.to_string()));
+    }
+
+    iter.next();
+    Ok(token)
+}
@@ -1,95 +1,223 @@
-use super::{IRExpression, IRFunction, IRNode};
+use std::io::{self, Write};
 
-fn print_expression(prefix: &str, exp: &IRExpression) {
-    let next_prefix = format!("{}  ", prefix);
+use super::{IRComparison, IRCondition, IRExpression, IRFunction, IRNode};
+
+fn print_expression<W: Write>(out: &mut W, prefix: &str, exp: &IRExpression) -> io::Result<()> {
+    let next_prefix = get_next_prefix(prefix);
     match exp {
-        &IRExpression::Value(ref value) => {
-            println!("{}Value: '{:?}'", prefix, value);
+        IRExpression::Value(ref value, _) => {
+            writeln!(out, "{}Value: '{:?}'", prefix, value)?;
         }
-        &IRExpression::Variable(ref name) => {
-            println!("{}Variable: '{:?}'", prefix, name);
+        IRExpression::Variable(ref name, _) => {
+            writeln!(out, "{}Variable: '{:?}'", prefix, name)?;
         }
-        &IRExpression::Operation(ref op, ref exps) => {
-            println!("{}Operation-'{:?}':", prefix, op);
+        IRExpression::Operation(ref op, ref exps, _) => {
+            writeln!(out, "{}Operation-'{:?}':", prefix, op)?;
             for exp in exps {
-                print_expression(&next_prefix, exp);
+                print_expression(out, &next_prefix, exp)?;
             }
         }
-        &IRExpression::Call(ref name, ref exp) => {
-            println!("{}Call-'{}':", prefix, name);
+        IRExpression::Call(ref name, ref exp, _) => {
+            writeln!(out, "{}Call-'{}':", prefix, name)?;
             for tmp in exp {
-                print_expression(&next_prefix, tmp);
+                print_expression(out, &next_prefix, tmp)?;
             }
         }
-        &IRExpression::Noop => {
-            println!("{}Noop", prefix);
+        IRExpression::Condition(ref cond, _) => {
+            writeln!(out, "{}Condition:", prefix)?;
+            print_condition(out, &next_prefix, cond)?;
+        }
+        IRExpression::Noop => {
+            writeln!(out, "{}Noop", prefix)?;
         }
     };
+
+    Ok(())
+}
+
+fn print_comparison<W: Write>(out: &mut W, prefix: &str, comp: &IRComparison) -> io::Result<()> {
+    let next_prefix = get_next_prefix(prefix);
+    let name = match comp {
+        IRComparison::Equals(_, _) => "Equals",
+        IRComparison::NotEquals(_, _) => "NotEquals",
+        IRComparison::LessThan(_, _) => "LessThan",
+        IRComparison::LessEqual(_, _) => "LessEqual",
+        IRComparison::GreaterThan(_, _) => "GreaterThan",
+        IRComparison::GreaterEqual(_, _) => "GreaterEqual",
+    };
+    let (left, right) = comp.operands();
+
+    writeln!(out, "{}Comparison-'{}':", prefix, name)?;
+    print_expression(out, &next_prefix, left)?;
+    print_expression(out, &next_prefix, right)
+}
+
+fn print_condition<W: Write>(out: &mut W, prefix: &str, cond: &IRCondition) -> io::Result<()> {
+    let next_prefix = get_next_prefix(prefix);
+    match cond {
+        IRCondition::Comparison(ref comp) => print_comparison(out, prefix, comp),
+        IRCondition::Expression(ref exp) => print_expression(out, prefix, exp),
+        IRCondition::And(ref left, ref right) => {
+            writeln!(out, "{}And:", prefix)?;
+            print_condition(out, &next_prefix, left)?;
+            print_condition(out, &next_prefix, right)
+        }
+        IRCondition::Or(ref left, ref right) => {
+            writeln!(out, "{}Or:", prefix)?;
+            print_condition(out, &next_prefix, left)?;
+            print_condition(out, &next_prefix, right)
+        }
+        IRCondition::Not(ref inner) => {
+            writeln!(out, "{}Not:", prefix)?;
+            print_condition(out, &next_prefix, inner)
+        }
+    }
 }
 
 fn get_next_prefix(current: &str) -> String {
     format!("{}  ", current)
 }
 
-fn print_node(prefix: &str, node: &IRNode) {
+fn print_node<W: Write>(out: &mut W, prefix: &str, node: &IRNode) -> io::Result<()> {
     let next_prefix = get_next_prefix(prefix);
     match node {
-        &IRNode::Assignment(ref name, ref exp) => {
-            println!("{}Assignment-'{}':", prefix, name);
-            print_expression(&next_prefix, exp);
+        IRNode::Assignment(ref name, ref exp, ref span) => {
+            writeln!(out, "{}Assignment-'{}' ({}):", prefix, name, span)?;
+            print_expression(out, &next_prefix, exp)?;
         }
-        &IRNode::DeclareVariable(ref name, ref exp) => {
-            println!("{}DeclareVariable-'{}':", prefix, name);
-            println!("{}{:?}", next_prefix, exp);
+        IRNode::DeclareVariable(ref name, ref var_type, ref init, ref span) => {
+            writeln!(out, "{}DeclareVariable-'{}' ({}):", prefix, name, span)?;
+            writeln!(out, "{}{:?}", next_prefix, var_type)?;
+            if let Some(init) = init {
+                print_expression(out, &next_prefix, init)?;
+            }
         }
-        &IRNode::Conditional(ref comparison, ref nodes) => {
-            println!("{}Conditional:", prefix);
-            println!("{}{:?}", next_prefix, comparison);
+        IRNode::Conditional(ref condition, ref nodes, ref else_nodes, ref span) => {
+            writeln!(out, "{}Conditional ({}):", prefix, span)?;
+            print_condition(out, &next_prefix, condition)?;
             let n_next_prefix = get_next_prefix(&next_prefix);
             for tmp in nodes {
-                print_nodes(&n_next_prefix, tmp);
+                print_nodes(out, &n_next_prefix, tmp)?;
+            }
+            if let Some(else_nodes) = else_nodes {
+                writeln!(out, "{}Else:", next_prefix)?;
+                for tmp in else_nodes {
+                    print_nodes(out, &n_next_prefix, tmp)?;
+                }
             }
         }
-        &IRNode::Loop(ref comparison, ref nodes) => {
-            println!("{}Loop:", prefix);
-            println!("{}{:?}", next_prefix, comparison);
+        IRNode::Loop(ref condition, ref nodes, ref span) => {
+            writeln!(out, "{}Loop ({}):", prefix, span)?;
+            print_condition(out, &next_prefix, condition)?;
             let n_next_prefix = get_next_prefix(&next_prefix);
             for tmp in nodes {
-                print_nodes(&n_next_prefix, tmp);
+                print_nodes(out, &n_next_prefix, tmp)?;
             }
         }
-        &IRNode::Return(ref raw_exp) => {
+        IRNode::Return(ref raw_exp, ref span) => {
             match raw_exp {
                 Some(exp) => {
-                    println!("{}Return:", prefix);
-                    print_expression(&next_prefix, exp);
+                    writeln!(out, "{}Return ({}):", prefix, span)?;
+                    print_expression(out, &next_prefix, exp)?;
                 }
-                None => println!("{}Return", prefix),
+                None => writeln!(out, "{}Return ({})", prefix, span)?,
             };
         }
-        &IRNode::SingleExpression(ref exp) => {
-            println!("{}Expression:", prefix);
-            print_expression(&next_prefix, exp);
+        IRNode::SingleExpression(ref exp, ref span) => {
+            writeln!(out, "{}Expression ({}):", prefix, span)?;
+            print_expression(out, &next_prefix, exp)?;
         }
     };
+
+    Ok(())
 }
 
-fn print_nodes(prefix: &str, nodes: &[IRNode]) {
+fn print_nodes<W: Write>(out: &mut W, prefix: &str, nodes: &[IRNode]) -> io::Result<()> {
     for node in nodes.iter() {
-        print_node(prefix, node);
+        print_node(out, prefix, node)?;
     }
+
+    Ok(())
 }
 
-pub fn pretty_print(ir: &std::collections::HashMap<String, IRFunction>) {
-    for (_, func) in ir {
-        println!("Function-'{}':", func.name);
-        println!("  Arguments:");
+/// Writes a human readable Form of the IR, with the Functions
+/// in the same Order as they appear in the Source-Code
+pub fn pretty_print<W: Write>(
+    ir: &std::collections::HashMap<String, IRFunction>,
+    out: &mut W,
+) -> io::Result<()> {
+    let mut functions: Vec<&IRFunction> = ir.values().collect();
+    functions.sort_by_key(|func| func.span.start);
+
+    for func in functions {
+        writeln!(out, "Function-'{}' ({}):", func.name, func.span)?;
+        if let Some(ref doc) = func.doc {
+            writeln!(out, "  Doc: {:?}", doc)?;
+        }
+        writeln!(out, "  Arguments:")?;
         for param in func.parameters.iter() {
-            println!("    {}: {:?}", param.name, param.param_type);
+            writeln!(out, "    {}: {:?}", param.name, param.param_type)?;
         }
         for statement in func.statements.iter() {
-            println!("  Statement:");
-            print_nodes("    ", statement);
+            writeln!(out, "  Statement:")?;
+            print_nodes(out, "    ", statement)?;
         }
     }
+
+    Ok(())
+}
+
+/// The same as `pretty_print`, but returns the Output as a String
+pub fn pretty_format(ir: &std::collections::HashMap<String, IRFunction>) -> String {
+    let mut result = Vec::new();
+    pretty_print(ir, &mut result).expect("Writing into a Vec can't fail");
+
+    String::from_utf8(result).expect("The IR is always printed as valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::frontend::parse;
+
+    #[test]
+    fn format_function() {
+        let ir = parse("func main() {\n  number x = 2;\n  print(x);\n}\n".to_owned()).unwrap();
+
+        let expected = "Function-'main' (1:6):
+  Arguments:
+  Statement:
+    DeclareVariable-'x' (2:3):
+      I64
+      Value: 'Number(2)'
+  Statement:
+    Expression (3:3):
+      Call-'print':
+        Variable: '\"x\"'
+";
+
+        assert_eq!(expected, pretty_format(&ir));
+    }
+
+    #[test]
+    fn doc_comment() {
+        let content = "/// Does nothing
+/// at all
+func main() {
+  /// Not attached to anything
+  return;
+}
+";
+        let ir = parse(content.to_owned()).unwrap();
+
+        let expected = "Function-'main' (3:6):
+  Doc: \"Does nothing\\nat all\"
+  Arguments:
+  Statement:
+    Return (5:3)
+";
+
+        assert_eq!(expected, pretty_format(&ir));
+    }
 }
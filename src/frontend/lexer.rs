@@ -1,30 +1,91 @@
+use std::fmt;
+
+use super::Span;
+
 mod tokenize;
 pub use tokenize::tokenize;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Primitives {
+    /// The default Number-Type, which is the same as 'i64'
     Number,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    Bool,
+    String,
+}
+
+impl Primitives {
+    /// The Name of the Type in the Source-Code
+    pub fn name(&self) -> &'static str {
+        match self {
+            Primitives::Number => "number",
+            Primitives::I8 => "i8",
+            Primitives::I16 => "i16",
+            Primitives::I32 => "i32",
+            Primitives::I64 => "i64",
+            Primitives::U8 => "u8",
+            Primitives::U16 => "u16",
+            Primitives::U32 => "u32",
+            Primitives::U64 => "u64",
+            Primitives::Bool => "bool",
+            Primitives::String => "string",
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum BuiltIns {
     Print,
+    PrintInt,
+    ReadChar,
+    ReadInt,
+    Eof,
+}
+
+impl BuiltIns {
+    /// The Name of the Builtin-Function in the Source-Code
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltIns::Print => "print",
+            BuiltIns::PrintInt => "print_int",
+            BuiltIns::ReadChar => "read_char",
+            BuiltIns::ReadInt => "read_int",
+            BuiltIns::Eof => "eof",
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Comparisons {
     Equal,
+    NotEqual,
+    LessThan,
+    LessEqual,
     GreaterThan,
+    GreaterEqual,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Token {
     Identifier(String),
-    ValueNumber(u64),
+    ValueNumber(i64),
+    ValueBool(bool),
+    /// A String-Literal with all the Escape-Sequences already replaced
+    ValueString(String),
+    ValueChar(char),
     Assignment,
     Plus,
     Minus,
     Multiply,
+    Divide,
+    Modulo,
     Semicolon,
     Colon,
     Comma,
@@ -33,11 +94,89 @@ pub enum Token {
     Primitive(Primitives),
     Builtin(BuiltIns),
     If,
+    Else,
     While,
     OpenCurly,
     ClosingCurly,
     Comparison(Comparisons),
+    And,
+    Or,
+    Not,
     Function,
     Return,
     Arrow,
+    /// A '///' Comment with the Text after it
+    DocComment(String),
+    /// Something that is not a valid Token, with the Reason for it
+    Error(String),
+    /// Marks the End of the Source-Code
+    EndOfFile,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Identifier(name) => write!(f, "identifier '{}'", name),
+            Token::ValueNumber(value) => write!(f, "number '{}'", value),
+            Token::ValueBool(value) => write!(f, "'{}'", value),
+            Token::ValueString(value) => write!(f, "string {:?}", value),
+            Token::ValueChar(value) => write!(f, "character {:?}", value),
+            Token::Assignment => write!(f, "'='"),
+            Token::Plus => write!(f, "'+'"),
+            Token::Minus => write!(f, "'-'"),
+            Token::Multiply => write!(f, "'*'"),
+            Token::Divide => write!(f, "'/'"),
+            Token::Modulo => write!(f, "'%'"),
+            Token::Semicolon => write!(f, "';'"),
+            Token::Colon => write!(f, "':'"),
+            Token::Comma => write!(f, "','"),
+            Token::OpenParan => write!(f, "'('"),
+            Token::ClosingParan => write!(f, "')'"),
+            Token::Primitive(ref prim) => write!(f, "type '{}'", prim.name()),
+            Token::Builtin(builtin) => write!(f, "builtin '{}'", builtin.name()),
+            Token::If => write!(f, "'if'"),
+            Token::Else => write!(f, "'else'"),
+            Token::While => write!(f, "'while'"),
+            Token::OpenCurly => write!(f, "'{{'"),
+            Token::ClosingCurly => write!(f, "'}}'"),
+            Token::Comparison(Comparisons::Equal) => write!(f, "'=='"),
+            Token::Comparison(Comparisons::NotEqual) => write!(f, "'!='"),
+            Token::Comparison(Comparisons::LessThan) => write!(f, "'<'"),
+            Token::Comparison(Comparisons::LessEqual) => write!(f, "'<='"),
+            Token::Comparison(Comparisons::GreaterThan) => write!(f, "'>'"),
+            Token::Comparison(Comparisons::GreaterEqual) => write!(f, "'>='"),
+            Token::And => write!(f, "'&&'"),
+            Token::Or => write!(f, "'||'"),
+            Token::Not => write!(f, "'!'"),
+            Token::Function => write!(f, "'func'"),
+            Token::Return => write!(f, "'return'"),
+            Token::Arrow => write!(f, "'->'"),
+            Token::DocComment(_) => write!(f, "doc comment"),
+            Token::Error(_) => write!(f, "invalid token"),
+            Token::EndOfFile => write!(f, "end of file"),
+        }
+    }
+}
+
+/// A single Token together with its Location in the Source-Code
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpannedToken {
+    pub token: Token,
+    pub span: Span,
+}
+
+impl SpannedToken {
+    pub fn new(token: Token, span: Span) -> Self {
+        Self { token, span }
+    }
+}
+
+/// Wraps all the given Tokens with an empty Span, which
+/// makes it easier to write the Tests for the Parser
+#[cfg(test)]
+pub fn with_default_spans(tokens: Vec<Token>) -> Vec<SpannedToken> {
+    tokens
+        .into_iter()
+        .map(|token| SpannedToken::new(token, Span::default()))
+        .collect()
 }
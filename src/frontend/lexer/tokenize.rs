@@ -1,62 +1,647 @@
-use super::{BuiltIns, Comparisons, Primitives, Token};
-
-fn parse(part: &str) -> Option<Token> {
-    match part {
-        "=" => Some(Token::Assignment),
-        "+" => Some(Token::Plus),
-        "-" => Some(Token::Minus),
-        "*" => Some(Token::Multiply),
-        "number" => Some(Token::Primitive(Primitives::Number)),
-        "print" => Some(Token::Builtin(BuiltIns::Print)),
-        "if" => Some(Token::If),
-        "while" => Some(Token::While),
-        "==" => Some(Token::Comparison(Comparisons::Equal)),
-        ">" => Some(Token::Comparison(Comparisons::GreaterThan)),
-        "func" => Some(Token::Function),
-        "->" => Some(Token::Arrow),
-        "return" => Some(Token::Return),
-        _ if part.len() > 0 => {
-            if let Ok(v) = part.parse() {
-                return Some(Token::ValueNumber(v));
-            }
+use std::{iter::Peekable, str::CharIndices};
 
-            Some(Token::Identifier(part.to_owned()))
-        }
+use super::{BuiltIns, Comparisons, Primitives, SpannedToken, Token};
+use crate::{error::CompileError, frontend::Span};
+
+/// The Token for a Word, which is either a Keyword or an Identifier
+fn keyword(word: &str) -> Token {
+    match word {
+        "number" => Token::Primitive(Primitives::Number),
+        "i8" => Token::Primitive(Primitives::I8),
+        "i16" => Token::Primitive(Primitives::I16),
+        "i32" => Token::Primitive(Primitives::I32),
+        "i64" => Token::Primitive(Primitives::I64),
+        "u8" => Token::Primitive(Primitives::U8),
+        "u16" => Token::Primitive(Primitives::U16),
+        "u32" => Token::Primitive(Primitives::U32),
+        "u64" => Token::Primitive(Primitives::U64),
+        "bool" => Token::Primitive(Primitives::Bool),
+        "string" => Token::Primitive(Primitives::String),
+        "true" => Token::ValueBool(true),
+        "false" => Token::ValueBool(false),
+        "print" => Token::Builtin(BuiltIns::Print),
+        "print_int" => Token::Builtin(BuiltIns::PrintInt),
+        "read_char" => Token::Builtin(BuiltIns::ReadChar),
+        "read_int" => Token::Builtin(BuiltIns::ReadInt),
+        "eof" => Token::Builtin(BuiltIns::Eof),
+        "if" => Token::If,
+        "else" => Token::Else,
+        "while" => Token::While,
+        "func" => Token::Function,
+        "return" => Token::Return,
+        _ => Token::Identifier(word.to_owned()),
+    }
+}
+
+/// The Token for an Operator made up of two Characters
+fn double_operator(first: char, second: char) -> Option<Token> {
+    match (first, second) {
+        ('=', '=') => Some(Token::Comparison(Comparisons::Equal)),
+        ('!', '=') => Some(Token::Comparison(Comparisons::NotEqual)),
+        ('<', '=') => Some(Token::Comparison(Comparisons::LessEqual)),
+        ('>', '=') => Some(Token::Comparison(Comparisons::GreaterEqual)),
+        ('&', '&') => Some(Token::And),
+        ('|', '|') => Some(Token::Or),
+        ('-', '>') => Some(Token::Arrow),
         _ => None,
     }
 }
 
-fn parse_seperator(chars: &str) -> Option<Token> {
-    match chars {
-        ";" => Some(Token::Semicolon),
-        ":" => Some(Token::Colon),
-        "," => Some(Token::Comma),
-        "(" => Some(Token::OpenParan),
-        ")" => Some(Token::ClosingParan),
-        "{" => Some(Token::OpenCurly),
-        "}" => Some(Token::ClosingCurly),
+/// The Token for a single Character of Punctuation
+fn single_operator(c: char) -> Option<Token> {
+    match c {
+        '=' => Some(Token::Assignment),
+        '+' => Some(Token::Plus),
+        '-' => Some(Token::Minus),
+        '*' => Some(Token::Multiply),
+        '/' => Some(Token::Divide),
+        '%' => Some(Token::Modulo),
+        '<' => Some(Token::Comparison(Comparisons::LessThan)),
+        '>' => Some(Token::Comparison(Comparisons::GreaterThan)),
+        '!' => Some(Token::Not),
+        ';' => Some(Token::Semicolon),
+        ':' => Some(Token::Colon),
+        ',' => Some(Token::Comma),
+        '(' => Some(Token::OpenParan),
+        ')' => Some(Token::ClosingParan),
+        '{' => Some(Token::OpenCurly),
+        '}' => Some(Token::ClosingCurly),
         _ => None,
     }
 }
 
-pub fn tokenize(content: String) -> Vec<Token> {
-    let mut result = Vec::new();
+/// The Token for a Number-Literal, which can start with a Prefix for
+/// its Base and contain Underscores between its Digits
+fn number(literal: &str) -> Token {
+    let (radix, digits) = match literal.get(..2) {
+        Some("0x") => (16, &literal[2..]),
+        Some("0b") => (2, &literal[2..]),
+        Some("0o") => (8, &literal[2..]),
+        _ => (10, literal),
+    };
+    let digits = digits.replace('_', "");
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Token::Error(format!("Invalid number '{}'", literal));
+    }
+
+    // All the Digits are valid, so this can only fail if the Value is too large.
+    // The Value one above the Maximum is kept as the Minimum, because it is
+    // needed to write the Minimum with a Minus, which is checked by the Parser
+    match u64::from_str_radix(&digits, radix) {
+        Ok(value) if value <= i64::MAX as u64 + 1 => Token::ValueNumber(value as i64),
+        _ => Token::Error(format!("Number '{}' is too large", literal)),
+    }
+}
+
+/// Used to turn Byte-Offsets into Line/Column pairs
+struct LineIndex<'a> {
+    content: &'a str,
+    /// The Offset of the Content in the entire Program
+    offset: usize,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(content: &'a str, offset: usize) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(content.match_indices('\n').map(|(index, _)| index + 1));
+
+        Self {
+            content,
+            offset,
+            line_starts,
+        }
+    }
+
+    fn span(&self, start: usize, end: usize) -> Span {
+        let line = match self.line_starts.binary_search(&start) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let column = self.content[self.line_starts[line]..start].chars().count() + 1;
+
+        Span::new(self.offset + start, self.offset + end, line + 1, column)
+    }
+}
+
+/// Goes through the Content Character by Character and always
+/// produces the longest possible Token
+struct Scanner<'a> {
+    content: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    lines: LineIndex<'a>,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(content: &'a str, offset: usize) -> Self {
+        Self {
+            content,
+            chars: content.char_indices().peekable(),
+            lines: LineIndex::new(content, offset),
+        }
+    }
+
+    /// The Byte-Offset of the next Character
+    fn position(&mut self) -> usize {
+        self.chars
+            .peek()
+            .map(|(index, _)| *index)
+            .unwrap_or(self.content.len())
+    }
 
-    let seperators = &[' ', '\n', ':', ';', ',', '(', ')', '{', '}'];
+    /// Consumes Characters as long as they match the Predicate
+    fn take_while<P>(&mut self, predicate: P)
+    where
+        P: Fn(char) -> bool,
+    {
+        while self.chars.next_if(|(_, c)| predicate(*c)).is_some() {}
+    }
+
+    /// Skips a Block-Comment, which can contain other nested Block-Comments,
+    /// where the opening '/*' was already consumed
+    fn skip_block_comment(&mut self, start: usize) -> Result<(), CompileError> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.chars.next().map(|(_, c)| c) {
+                Some('/') if self.chars.next_if(|(_, c)| *c == '*').is_some() => depth += 1,
+                Some('*') if self.chars.next_if(|(_, c)| *c == '/').is_some() => depth -= 1,
+                Some(_) => {}
+                None => {
+                    return Err(CompileError::lex(
+                        "Unterminated block comment",
+                        self.lines.span(start, start + 2),
+                    ))
+                }
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Reads an Escape-Sequence after the '\' and returns the Character
+    /// it stands for, or None if the Line ends before it
+    fn escape(&mut self) -> Option<Result<char, String>> {
+        let (_, c) = self.chars.next_if(|(_, c)| *c != '\n')?;
+
+        let result = match c {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            'r' => Ok('\r'),
+            '0' => Ok('\0'),
+            '\\' => Ok('\\'),
+            '"' => Ok('"'),
+            '\'' => Ok('\''),
+            // Exactly two Hex-Digits, which are limited to ASCII
+            'x' => {
+                let mut digits = String::new();
+                while digits.len() < 2 {
+                    match self.chars.next_if(|(_, c)| c.is_ascii_hexdigit()) {
+                        Some((_, digit)) => digits.push(digit),
+                        None => break,
+                    };
+                }
+
+                match u8::from_str_radix(&digits, 16) {
+                    Ok(value) if digits.len() == 2 && value <= 0x7F => Ok(value as char),
+                    Ok(_) if digits.len() == 2 => {
+                        Err(format!("Hex escape '\\x{}' is out of range", digits))
+                    }
+                    _ => Err(format!("Invalid hex escape '\\x{}'", digits)),
+                }
+            }
+            other => Err(format!("Unknown escape sequence '\\{}'", other)),
+        };
+
+        Some(result)
+    }
+
+    /// Reads the Characters up until the closing Quote, where the opening
+    /// one was already consumed, and replaces all the Escape-Sequences
+    fn quoted(&mut self, quote: char, name: &str) -> Result<String, String> {
+        let unterminated = || format!("Unterminated {}", name);
+        let mut value = String::new();
+        let mut invalid = None;
+
+        loop {
+            let (_, c) = self
+                .chars
+                .next_if(|(_, c)| *c != '\n')
+                .ok_or_else(unterminated)?;
+
+            match c {
+                _ if c == quote => break,
+                '\\' => match self.escape() {
+                    Some(Ok(escaped)) => value.push(escaped),
+                    // The Rest is still read, so that the Scanner
+                    // continues after the closing Quote
+                    Some(Err(message)) => {
+                        invalid.get_or_insert(message);
+                    }
+                    None => return Err(unterminated()),
+                },
+                _ => value.push(c),
+            };
+        }
 
-    let mut searching = &content[..];
-    while let Some(index) = searching.find(&seperators[..]) {
-        let raw_part = &searching[..index];
-        let part = raw_part.trim_start();
-        if let Some(t) = parse(part) {
-            result.push(t);
+        match invalid {
+            Some(message) => Err(message),
+            None => Ok(value),
         }
-        if let Some(t) = parse_seperator(searching.get(index..index + 1).unwrap()) {
-            result.push(t);
+    }
+
+    fn string_literal(&mut self) -> Token {
+        match self.quoted('"', "string") {
+            Ok(value) => Token::ValueString(value),
+            Err(message) => Token::Error(message),
+        }
+    }
+
+    fn char_literal(&mut self) -> Token {
+        let value = match self.quoted('\'', "character literal") {
+            Ok(value) => value,
+            Err(message) => return Token::Error(message),
+        };
+
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            // The same Limit as for Hex-Escapes, because the Character is
+            // printed as a single Byte
+            (Some(c), None) if !c.is_ascii() => {
+                Token::Error(format!("Character '{}' is not ASCII", c))
+            }
+            (Some(c), None) => Token::ValueChar(c),
+            (None, _) => Token::Error("Empty character literal".to_owned()),
+            (Some(_), Some(_)) => {
+                Token::Error("Character literals can only contain a single character".to_owned())
+            }
+        }
+    }
+
+    /// Produces the next Token, or None once the End of the Content is reached
+    fn next_token(&mut self) -> Result<Option<SpannedToken>, CompileError> {
+        loop {
+            let (start, c) = match self.chars.next() {
+                Some(next) => next,
+                None => return Ok(None),
+            };
+
+            let token = match c {
+                _ if c.is_whitespace() => continue,
+                '/' if self.chars.next_if(|(_, c)| *c == '/').is_some() => {
+                    let text_start = self.position();
+                    self.take_while(|c| c != '\n');
+                    let end = self.position();
+                    let comment = &self.content[text_start..end];
+
+                    // A '////' Line is not a Doc-Comment, but just a normal Comment
+                    match comment.strip_prefix('/') {
+                        Some(text) if !text.starts_with('/') => {
+                            let text = text.trim_end();
+                            let text = text.strip_prefix(' ').unwrap_or(text);
+                            Token::DocComment(text.to_owned())
+                        }
+                        _ => continue,
+                    }
+                }
+                '/' if self.chars.next_if(|(_, c)| *c == '*').is_some() => {
+                    self.skip_block_comment(start)?;
+                    continue;
+                }
+                '"' => self.string_literal(),
+                '\'' => self.char_literal(),
+                _ if c.is_ascii_alphabetic() || c == '_' => {
+                    self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+                    keyword(&self.content[start..self.position()])
+                }
+                _ if c.is_ascii_digit() => {
+                    // Letters directly after a Number are part of the Literal,
+                    // so that '1x' is reported as a whole
+                    self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+                    number(&self.content[start..self.position()])
+                }
+                _ => {
+                    let double = self
+                        .chars
+                        .peek()
+                        .and_then(|(_, second)| double_operator(c, *second));
+                    match double {
+                        Some(token) => {
+                            self.chars.next();
+                            token
+                        }
+                        None => match single_operator(c) {
+                            Some(token) => token,
+                            None => Token::Error(format!("Unknown character '{}'", c)),
+                        },
+                    }
+                }
+            };
+
+            let end = self.position();
+            return Ok(Some(SpannedToken::new(token, self.lines.span(start, end))));
         }
+    }
+}
+
+/// Splits the Content into Tokens, where all the Spans are shifted
+/// by the given Offset
+pub fn tokenize(content: &str, offset: usize) -> Result<Vec<SpannedToken>, CompileError> {
+    let mut result = Vec::new();
+
+    let mut scanner = Scanner::new(content, offset);
+    while let Some(token) = scanner.next_token()? {
+        result.push(token);
+    }
+
+    result.push(SpannedToken::new(
+        Token::EndOfFile,
+        scanner.lines.span(content.len(), content.len()),
+    ));
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_single_line() {
+        let content = "number x = 5;".to_owned();
+
+        let expected = vec![
+            SpannedToken::new(Token::Primitive(Primitives::Number), Span::new(0, 6, 1, 1)),
+            SpannedToken::new(Token::Identifier("x".to_owned()), Span::new(7, 8, 1, 8)),
+            SpannedToken::new(Token::Assignment, Span::new(9, 10, 1, 10)),
+            SpannedToken::new(Token::ValueNumber(5), Span::new(11, 12, 1, 12)),
+            SpannedToken::new(Token::Semicolon, Span::new(12, 13, 1, 13)),
+            SpannedToken::new(Token::EndOfFile, Span::new(13, 13, 1, 14)),
+        ];
+
+        assert_eq!(Ok(expected), tokenize(&content, 0));
+    }
+
+    #[test]
+    fn spans_multiple_lines() {
+        let content = "func main() {\n\tprint(1);\n}\n".to_owned();
+
+        let expected = vec![
+            SpannedToken::new(Token::Function, Span::new(0, 4, 1, 1)),
+            SpannedToken::new(Token::Identifier("main".to_owned()), Span::new(5, 9, 1, 6)),
+            SpannedToken::new(Token::OpenParan, Span::new(9, 10, 1, 10)),
+            SpannedToken::new(Token::ClosingParan, Span::new(10, 11, 1, 11)),
+            SpannedToken::new(Token::OpenCurly, Span::new(12, 13, 1, 13)),
+            SpannedToken::new(Token::Builtin(BuiltIns::Print), Span::new(15, 20, 2, 2)),
+            SpannedToken::new(Token::OpenParan, Span::new(20, 21, 2, 7)),
+            SpannedToken::new(Token::ValueNumber(1), Span::new(21, 22, 2, 8)),
+            SpannedToken::new(Token::ClosingParan, Span::new(22, 23, 2, 9)),
+            SpannedToken::new(Token::Semicolon, Span::new(23, 24, 2, 10)),
+            SpannedToken::new(Token::ClosingCurly, Span::new(25, 26, 3, 1)),
+            SpannedToken::new(Token::EndOfFile, Span::new(27, 27, 4, 1)),
+        ];
+
+        assert_eq!(Ok(expected), tokenize(&content, 0));
+    }
+
+    #[test]
+    fn spans_with_offset() {
+        let content = "x;".to_owned();
+
+        let expected = vec![
+            SpannedToken::new(Token::Identifier("x".to_owned()), Span::new(20, 21, 1, 1)),
+            SpannedToken::new(Token::Semicolon, Span::new(21, 22, 1, 2)),
+            SpannedToken::new(Token::EndOfFile, Span::new(22, 22, 1, 3)),
+        ];
+
+        assert_eq!(Ok(expected), tokenize(&content, 20));
+    }
+
+    #[test]
+    fn invalid_tokens() {
+        let content = "number 1x = 5 $;".to_owned();
+
+        let expected = vec![
+            SpannedToken::new(Token::Primitive(Primitives::Number), Span::new(0, 6, 1, 1)),
+            SpannedToken::new(
+                Token::Error("Invalid number '1x'".to_owned()),
+                Span::new(7, 9, 1, 8),
+            ),
+            SpannedToken::new(Token::Assignment, Span::new(10, 11, 1, 11)),
+            SpannedToken::new(Token::ValueNumber(5), Span::new(12, 13, 1, 13)),
+            SpannedToken::new(
+                Token::Error("Unknown character '$'".to_owned()),
+                Span::new(14, 15, 1, 15),
+            ),
+            SpannedToken::new(Token::Semicolon, Span::new(15, 16, 1, 16)),
+            SpannedToken::new(Token::EndOfFile, Span::new(16, 16, 1, 17)),
+        ];
+
+        assert_eq!(Ok(expected), tokenize(&content, 0));
+    }
+
+    #[test]
+    fn without_whitespace() {
+        let content = "x==1->a+b>=c\r\n\tend".to_owned();
+
+        let tokens: Vec<Token> = tokenize(&content, 0)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.token)
+            .collect();
+        let expected = vec![
+            Token::Identifier("x".to_owned()),
+            Token::Comparison(Comparisons::Equal),
+            Token::ValueNumber(1),
+            Token::Arrow,
+            Token::Identifier("a".to_owned()),
+            Token::Plus,
+            Token::Identifier("b".to_owned()),
+            Token::Comparison(Comparisons::GreaterEqual),
+            Token::Identifier("c".to_owned()),
+            Token::Identifier("end".to_owned()),
+            Token::EndOfFile,
+        ];
+
+        assert_eq!(expected, tokens);
+    }
+
+    #[test]
+    fn comparisons() {
+        let content = "a != b >= c;".to_owned();
+
+        let expected = vec![
+            SpannedToken::new(Token::Identifier("a".to_owned()), Span::new(0, 1, 1, 1)),
+            SpannedToken::new(
+                Token::Comparison(Comparisons::NotEqual),
+                Span::new(2, 4, 1, 3),
+            ),
+            SpannedToken::new(Token::Identifier("b".to_owned()), Span::new(5, 6, 1, 6)),
+            SpannedToken::new(
+                Token::Comparison(Comparisons::GreaterEqual),
+                Span::new(7, 9, 1, 8),
+            ),
+            SpannedToken::new(Token::Identifier("c".to_owned()), Span::new(10, 11, 1, 11)),
+            SpannedToken::new(Token::Semicolon, Span::new(11, 12, 1, 12)),
+            SpannedToken::new(Token::EndOfFile, Span::new(12, 12, 1, 13)),
+        ];
+
+        assert_eq!(Ok(expected), tokenize(&content, 0));
+    }
+
+    #[test]
+    fn negative_literal() {
+        let content = "x = -5;".to_owned();
+
+        let expected = vec![
+            SpannedToken::new(Token::Identifier("x".to_owned()), Span::new(0, 1, 1, 1)),
+            SpannedToken::new(Token::Assignment, Span::new(2, 3, 1, 3)),
+            SpannedToken::new(Token::Minus, Span::new(4, 5, 1, 5)),
+            SpannedToken::new(Token::ValueNumber(5), Span::new(5, 6, 1, 6)),
+            SpannedToken::new(Token::Semicolon, Span::new(6, 7, 1, 7)),
+            SpannedToken::new(Token::EndOfFile, Span::new(7, 7, 1, 8)),
+        ];
+
+        assert_eq!(Ok(expected), tokenize(&content, 0));
+    }
+
+    #[test]
+    fn not_before_name() {
+        let content = "!!flag != x;".to_owned();
+
+        let expected = vec![
+            SpannedToken::new(Token::Not, Span::new(0, 1, 1, 1)),
+            SpannedToken::new(Token::Not, Span::new(1, 2, 1, 2)),
+            SpannedToken::new(Token::Identifier("flag".to_owned()), Span::new(2, 6, 1, 3)),
+            SpannedToken::new(
+                Token::Comparison(Comparisons::NotEqual),
+                Span::new(7, 9, 1, 8),
+            ),
+            SpannedToken::new(Token::Identifier("x".to_owned()), Span::new(10, 11, 1, 11)),
+            SpannedToken::new(Token::Semicolon, Span::new(11, 12, 1, 12)),
+            SpannedToken::new(Token::EndOfFile, Span::new(12, 12, 1, 13)),
+        ];
+
+        assert_eq!(Ok(expected), tokenize(&content, 0));
+    }
+
+    #[test]
+    fn comments() {
+        let content = "x // y;\n/* a /* b; */ c; */ z;\n/// Doc ü\n".to_owned();
+
+        let expected = vec![
+            SpannedToken::new(Token::Identifier("x".to_owned()), Span::new(0, 1, 1, 1)),
+            SpannedToken::new(Token::Identifier("z".to_owned()), Span::new(28, 29, 2, 21)),
+            SpannedToken::new(Token::Semicolon, Span::new(29, 30, 2, 22)),
+            SpannedToken::new(
+                Token::DocComment("Doc ü".to_owned()),
+                Span::new(31, 41, 3, 1),
+            ),
+            SpannedToken::new(Token::EndOfFile, Span::new(42, 42, 4, 1)),
+        ];
 
-        searching = &searching[index + 1..];
+        assert_eq!(Ok(expected), tokenize(&content, 0));
     }
 
-    result
+    #[test]
+    fn unterminated_block_comment() {
+        let content = "x; /* a /* b */\n".to_owned();
+
+        assert_eq!(
+            Err(CompileError::lex(
+                "Unterminated block comment",
+                Span::new(3, 5, 1, 4)
+            )),
+            tokenize(&content, 0)
+        );
+    }
+
+    #[test]
+    fn string_literals() {
+        let content = "\"a\\\"b\\n\" \"\\q\" \"open\n".to_owned();
+
+        let expected = vec![
+            SpannedToken::new(
+                Token::ValueString("a\"b\n".to_owned()),
+                Span::new(0, 8, 1, 1),
+            ),
+            SpannedToken::new(
+                Token::Error("Unknown escape sequence '\\q'".to_owned()),
+                Span::new(9, 13, 1, 10),
+            ),
+            SpannedToken::new(
+                Token::Error("Unterminated string".to_owned()),
+                Span::new(14, 19, 1, 15),
+            ),
+            SpannedToken::new(Token::EndOfFile, Span::new(20, 20, 2, 1)),
+        ];
+
+        assert_eq!(Ok(expected), tokenize(&content, 0));
+    }
+
+    #[test]
+    fn char_literals() {
+        let content = r"'a' '\n' '\x41' '\'' '' 'ab' '\x80' '\xg' 'ü' 'a".to_owned();
+
+        let expected = vec![
+            SpannedToken::new(Token::ValueChar('a'), Span::new(0, 3, 1, 1)),
+            SpannedToken::new(Token::ValueChar('\n'), Span::new(4, 8, 1, 5)),
+            SpannedToken::new(Token::ValueChar('A'), Span::new(9, 15, 1, 10)),
+            SpannedToken::new(Token::ValueChar('\''), Span::new(16, 20, 1, 17)),
+            SpannedToken::new(
+                Token::Error("Empty character literal".to_owned()),
+                Span::new(21, 23, 1, 22),
+            ),
+            SpannedToken::new(
+                Token::Error("Character literals can only contain a single character".to_owned()),
+                Span::new(24, 28, 1, 25),
+            ),
+            SpannedToken::new(
+                Token::Error("Hex escape '\\x80' is out of range".to_owned()),
+                Span::new(29, 35, 1, 30),
+            ),
+            SpannedToken::new(
+                Token::Error("Invalid hex escape '\\x'".to_owned()),
+                Span::new(36, 41, 1, 37),
+            ),
+            SpannedToken::new(
+                Token::Error("Character 'ü' is not ASCII".to_owned()),
+                Span::new(42, 46, 1, 43),
+            ),
+            SpannedToken::new(
+                Token::Error("Unterminated character literal".to_owned()),
+                Span::new(47, 49, 1, 47),
+            ),
+            SpannedToken::new(Token::EndOfFile, Span::new(49, 49, 1, 49)),
+        ];
+
+        assert_eq!(Ok(expected), tokenize(&content, 0));
+    }
+
+    #[test]
+    fn number_literals() {
+        let content =
+            "0xFF 0b1010 0o17 1_000_000 0x 0b12 9223372036854775808 9223372036854775809 0x1_0000_0000_0000_0000"
+                .to_owned();
+
+        let tokens: Vec<Token> = tokenize(&content, 0)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.token)
+            .collect();
+        let expected = vec![
+            Token::ValueNumber(255),
+            Token::ValueNumber(10),
+            Token::ValueNumber(15),
+            Token::ValueNumber(1_000_000),
+            Token::Error("Invalid number '0x'".to_owned()),
+            Token::Error("Invalid number '0b12'".to_owned()),
+            Token::ValueNumber(i64::MIN),
+            Token::Error("Number '9223372036854775809' is too large".to_owned()),
+            Token::Error("Number '0x1_0000_0000_0000_0000' is too large".to_owned()),
+            Token::EndOfFile,
+        ];
+
+        assert_eq!(expected, tokens);
+    }
 }
@@ -0,0 +1,905 @@
+use std::collections::HashMap;
+
+use crate::error::{CompileError, CompileWarning};
+
+use super::{
+    ir::{
+        IRComparison, IRCondition, IRExpression, IRFunction, IRNode, IROperation, IRType, IRValue,
+    },
+    Span,
+};
+
+/// The Labels that are already used by the Builtins in the generated
+/// Assembly, which can't be used as Function-Names
+pub const RESERVED_NAMES: &[&str] = &[
+    "_start",
+    "print",
+    "print_string",
+    "print_int",
+    "print_int_digit",
+    "print_int_positive",
+    "print_int_write",
+    "read_char",
+    "read_char_buffered",
+    "read_char_filled",
+    "read_char_end",
+    "read_int",
+    "read_int_skip",
+    "read_int_check",
+    "read_int_first",
+    "read_int_digit",
+    "read_int_done",
+    "read_int_last",
+    "read_int_end",
+    "eof",
+    "read_buffer",
+    "read_position",
+    "read_length",
+    "read_eof",
+];
+
+/// The Names of the Registers and the Size-Keywords of the Assembler,
+/// apart from the numbered Registers 'r8' to 'r15'
+const ASSEMBLY_KEYWORDS: &[&str] = &[
+    "byte", "word", "dword", "qword", "rel", "rax", "rbx", "rcx", "rdx", "rsi", "rdi", "rbp",
+    "rsp", "eax", "ebx", "ecx", "edx", "esi", "edi", "ebp", "esp", "ax", "bx", "cx", "dx", "si",
+    "di", "bp", "sp", "al", "bl", "cl", "dl", "sil", "dil", "bpl", "spl", "ah", "bh", "ch", "dh",
+];
+
+/// Checks if the Name is a Keyword of the Assembler, which can't be used
+/// as a Label. The Assembler ignores the Case of these Keywords
+pub fn is_assembly_keyword(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    if ASSEMBLY_KEYWORDS.contains(&name.as_str()) {
+        return true;
+    }
+
+    // The numbered Registers can have a Suffix for their lower Parts
+    match name.strip_prefix('r') {
+        Some(rest) => {
+            let number = rest.strip_suffix(&['d', 'w', 'b'][..]).unwrap_or(rest);
+            (8..=15).any(|n: u8| n.to_string() == number)
+        }
+        None => false,
+    }
+}
+
+/// The Parameters and the Return-Type of a Function
+struct Signature {
+    parameters: Vec<IRType>,
+    return_type: Option<IRType>,
+}
+
+/// The Signatures of the Functions that are provided by the Backend,
+/// which can depend on the Types of the Arguments
+fn builtin_signature(name: &str, arg_types: &[Option<IRType>]) -> Option<Signature> {
+    match name {
+        // Strings are printed entirely, while Numbers are printed as a single Byte
+        "print" => Some(Signature {
+            parameters: match arg_types.first() {
+                Some(Some(IRType::String)) => vec![IRType::String],
+                _ => vec![IRType::I64],
+            },
+            return_type: None,
+        }),
+        "print_int" => Some(Signature {
+            parameters: vec![IRType::I64],
+            return_type: None,
+        }),
+        // Both return -1 or 0 at the End of the Input, which is reported by 'eof'
+        "read_char" | "read_int" => Some(Signature {
+            parameters: vec![],
+            return_type: Some(IRType::I64),
+        }),
+        "eof" => Some(Signature {
+            parameters: vec![],
+            return_type: Some(IRType::Bool),
+        }),
+        _ => None,
+    }
+}
+
+/// The Value of an Expression that only consists of Number-Literals,
+/// which wraps around the same as it does at runtime
+fn constant(exp: &IRExpression) -> Option<i64> {
+    match exp {
+        IRExpression::Value(IRValue::Number(value), _) => Some(*value),
+        IRExpression::Operation(ref op, ref exps, _) => {
+            let left = constant(exps.first()?)?;
+            let right = constant(exps.get(1)?)?;
+
+            match op {
+                IROperation::Add => Some(left.wrapping_add(right)),
+                IROperation::Sub => Some(left.wrapping_sub(right)),
+                IROperation::Multiply => Some(left.wrapping_mul(right)),
+                IROperation::Divide | IROperation::Modulo if right == 0 => None,
+                IROperation::Divide => Some(left.wrapping_div(right)),
+                IROperation::Modulo => Some(left.wrapping_rem(right)),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Checks if the Value can be represented by the Integer-Type
+/// without being truncated
+fn fits(var_type: &IRType, value: i64) -> bool {
+    let bits = var_type.size() as u32 * 8;
+    let value = value as i128;
+
+    match var_type.is_signed() {
+        true => (-(1i128 << (bits - 1))..1i128 << (bits - 1)).contains(&value),
+        false => (0..1i128 << bits).contains(&value),
+    }
+}
+
+/// Checks if a Value of the found Type can be used where the
+/// expected Type is needed, which is true for all Integers
+fn compatible(expected: &IRType, found: &IRType) -> bool {
+    expected == found || (expected.is_integer() && found.is_integer())
+}
+
+/// A declared Variable and whether it definitely holds a Value
+#[derive(Clone)]
+struct Binding {
+    var_type: IRType,
+    initialized: bool,
+}
+
+type Scopes = Vec<HashMap<String, Binding>>;
+
+struct Checker<'a> {
+    functions: &'a HashMap<String, IRFunction>,
+    /// The Variables of every Block that is currently open, with
+    /// the innermost Block last
+    scopes: Scopes,
+    errors: Vec<CompileError>,
+    warnings: Vec<CompileWarning>,
+}
+
+impl<'a> Checker<'a> {
+    fn error<S: Into<String>>(&mut self, message: S, span: Span) {
+        self.errors.push(CompileError::semantic(message, span));
+    }
+
+    fn warn<S: Into<String>>(&mut self, message: S, span: Span) {
+        self.warnings.push(CompileWarning::new(message, span));
+    }
+
+    /// Finds the Variable in the innermost Block that declares it
+    fn lookup(&mut self, name: &str) -> Option<&mut Binding> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+    }
+
+    /// Only keeps the Variables initialized that were also initialized
+    /// on the other Path through the Program
+    fn merge(&mut self, other: &Scopes) {
+        for (scope, other_scope) in self.scopes.iter_mut().zip(other) {
+            for (name, binding) in scope.iter_mut() {
+                binding.initialized &= other_scope[name].initialized;
+            }
+        }
+    }
+
+    /// Declares the Variable in the innermost Block, where it shadows
+    /// Variables with the same Name from the outer Blocks
+    fn declare(&mut self, name: &str, var_type: &IRType, initialized: bool, span: Span) {
+        let scope = self
+            .scopes
+            .last_mut()
+            .expect("There is always an open Block");
+        if scope.contains_key(name) {
+            self.error(format!("Variable '{}' is already declared", name), span);
+            return;
+        }
+
+        scope.insert(
+            name.to_owned(),
+            Binding {
+                var_type: var_type.clone(),
+                initialized,
+            },
+        );
+    }
+
+    fn signature(&self, name: &str, arg_types: &[Option<IRType>]) -> Option<Signature> {
+        match self.functions.get(name) {
+            Some(func) => Some(Signature {
+                parameters: func
+                    .parameters
+                    .iter()
+                    .map(|param| param.param_type.clone())
+                    .collect(),
+                return_type: func.return_type.clone(),
+            }),
+            None => builtin_signature(name, arg_types),
+        }
+    }
+
+    /// Checks the Call and returns the Return-Type of the called Function,
+    /// if the Function exists
+    fn check_call(
+        &mut self,
+        name: &str,
+        args: &[IRExpression],
+        span: Span,
+    ) -> Option<Option<IRType>> {
+        let arg_types: Vec<Option<IRType>> =
+            args.iter().map(|arg| self.check_expression(arg)).collect();
+
+        let signature = match self.signature(name, &arg_types) {
+            Some(s) => s,
+            None => {
+                self.error(format!("Unknown function '{}'", name), span);
+                return None;
+            }
+        };
+
+        if signature.parameters.len() != args.len() {
+            self.error(
+                format!(
+                    "Function '{}' expects {} arguments, found {}",
+                    name,
+                    signature.parameters.len(),
+                    args.len()
+                ),
+                span,
+            );
+        }
+
+        for ((expected, found), arg) in signature.parameters.iter().zip(arg_types).zip(args) {
+            match found {
+                Some(found) if !compatible(expected, &found) => self.error(
+                    format!("Expected an argument of type {}, found {}", expected, found),
+                    arg.span(),
+                ),
+                Some(_) => self.check_fits(expected, arg),
+                None => {}
+            };
+        }
+
+        Some(signature.return_type)
+    }
+
+    /// Checks the Expression and returns its Type, if it could be determined
+    fn check_expression(&mut self, exp: &IRExpression) -> Option<IRType> {
+        match exp {
+            IRExpression::Value(IRValue::Number(_), _) => Some(IRType::I64),
+            IRExpression::Value(IRValue::Bool(_), _) => Some(IRType::Bool),
+            IRExpression::Value(IRValue::String(_), _) => Some(IRType::String),
+            IRExpression::Variable(ref name, span) => {
+                let (var_type, initialized) = match self.lookup(name) {
+                    Some(binding) => {
+                        // Only the first Read of the Variable is reported
+                        let initialized = binding.initialized;
+                        binding.initialized = true;
+                        (binding.var_type.clone(), initialized)
+                    }
+                    None => {
+                        self.error(format!("Unknown variable '{}'", name), *span);
+                        return None;
+                    }
+                };
+
+                if !initialized {
+                    self.warn(
+                        format!("Variable '{}' is used before it is initialized", name),
+                        *span,
+                    );
+                }
+                Some(var_type)
+            }
+            IRExpression::Operation(ref op, ref exps, span) => {
+                let types: Vec<Option<IRType>> =
+                    exps.iter().map(|exp| self.check_expression(exp)).collect();
+
+                // This is checked here instead of when folding Constants, so
+                // that it does not depend on the Optimization-Level
+                let divides = matches!(op, IROperation::Divide | IROperation::Modulo);
+                if divides && exps.get(1).and_then(constant) == Some(0) {
+                    self.error("Division by zero", *span);
+                }
+
+                let mut result = Some(IRType::I64);
+                for (exp_type, exp) in types.into_iter().zip(exps) {
+                    match exp_type {
+                        Some(ref t) if !t.is_integer() => {
+                            self.error(
+                                format!("Cant use {:?} on a value of type {}", op, t),
+                                exp.span(),
+                            );
+                            result = None;
+                        }
+                        Some(_) => {}
+                        None => result = None,
+                    };
+                }
+
+                result
+            }
+            IRExpression::Call(ref name, ref args, span) => {
+                match self.check_call(name, args, *span)? {
+                    Some(ret_type) => Some(ret_type),
+                    None => {
+                        self.error(
+                            format!("Function '{}' does not return a value", name),
+                            *span,
+                        );
+                        None
+                    }
+                }
+            }
+            IRExpression::Condition(ref cond, _) => {
+                self.check_condition(cond);
+                Some(IRType::Bool)
+            }
+            IRExpression::Noop => None,
+        }
+    }
+
+    fn check_condition(&mut self, cond: &IRCondition) {
+        match cond {
+            IRCondition::Comparison(ref comp) => {
+                let (left, right) = comp.operands();
+                let left_type = self.check_expression(left);
+                let right_type = self.check_expression(right);
+
+                if let (Some(left_type), Some(right_type)) = (left_type, right_type) {
+                    let is_equality = matches!(
+                        comp,
+                        IRComparison::Equals(_, _) | IRComparison::NotEquals(_, _)
+                    );
+                    let valid = (left_type.is_integer() && right_type.is_integer())
+                        || (is_equality && left_type == IRType::Bool && right_type == IRType::Bool);
+
+                    if !valid {
+                        self.error(
+                            format!("Cant compare {} with {}", left_type, right_type),
+                            left.span().merge(&right.span()),
+                        );
+                    }
+                }
+            }
+            IRCondition::Expression(ref exp) => match self.check_expression(exp) {
+                Some(IRType::Bool) | None => {}
+                Some(found) => self.error(
+                    format!("Expected a condition of type bool, found {}", found),
+                    exp.span(),
+                ),
+            },
+            IRCondition::And(ref left, ref right) | IRCondition::Or(ref left, ref right) => {
+                self.check_condition(left);
+                self.check_condition(right);
+            }
+            IRCondition::Not(ref inner) => self.check_condition(inner),
+        };
+    }
+
+    /// Checks the Statements of a Block, whose Variables are only
+    /// visible inside of it
+    fn check_block(&mut self, statements: &[Vec<IRNode>], func: &IRFunction) {
+        self.scopes.push(HashMap::new());
+        for statement in statements.iter() {
+            for node in statement.iter() {
+                self.check_node(node, func);
+            }
+        }
+        self.scopes.pop();
+    }
+
+    /// Checks that the Expression can be stored in the Variable
+    /// Warns if the Expression is a Constant, that gets truncated when
+    /// it is stored as the Integer-Type
+    fn check_fits(&mut self, var_type: &IRType, exp: &IRExpression) {
+        if !var_type.is_integer() {
+            return;
+        }
+
+        if let Some(value) = constant(exp) {
+            if !fits(var_type, value) {
+                self.warn(
+                    format!("Value {} does not fit into {}", value, var_type),
+                    exp.span(),
+                );
+            }
+        }
+    }
+
+    fn check_assigned(&mut self, name: &str, var_type: &IRType, exp: &IRExpression) {
+        match self.check_expression(exp) {
+            Some(exp_type) if !compatible(var_type, &exp_type) => {
+                self.error(
+                    format!(
+                        "Cant assign a value of type {} to '{}' of type {}",
+                        exp_type, name, var_type
+                    ),
+                    exp.span(),
+                );
+            }
+            Some(_) => self.check_fits(var_type, exp),
+            None => {}
+        };
+    }
+
+    fn check_node(&mut self, node: &IRNode, func: &IRFunction) {
+        match node {
+            IRNode::DeclareVariable(ref name, ref var_type, ref init, span) => {
+                // The initial Value is checked before the new Variable
+                // shadows any other one
+                if let Some(exp) = init {
+                    self.check_assigned(name, var_type, exp);
+                }
+
+                self.declare(name, var_type, init.is_some(), *span);
+            }
+            IRNode::Assignment(ref name, ref exp, span) => {
+                match self.lookup(name).map(|binding| binding.var_type.clone()) {
+                    Some(var_type) => self.check_assigned(name, &var_type, exp),
+                    None => {
+                        self.check_expression(exp);
+                        self.error(format!("Unknown variable '{}'", name), *span);
+                    }
+                };
+
+                if let Some(binding) = self.lookup(name) {
+                    binding.initialized = true;
+                }
+            }
+            IRNode::SingleExpression(IRExpression::Call(ref name, ref args, span), _) => {
+                self.check_call(name, args, *span);
+            }
+            IRNode::SingleExpression(ref exp, _) => {
+                self.check_expression(exp);
+            }
+            IRNode::Conditional(ref cond, ref inner, ref else_inner, _) => {
+                self.check_condition(cond);
+
+                let before = self.scopes.clone();
+                self.check_block(inner, func);
+                let after_then = std::mem::replace(&mut self.scopes, before);
+                if let Some(else_inner) = else_inner {
+                    self.check_block(else_inner, func);
+                }
+
+                // A Branch that always returns does not continue after
+                // the Conditional
+                let else_returns = else_inner.as_ref().is_some_and(|e| always_returns(e));
+                if else_returns {
+                    self.scopes = after_then;
+                } else if !always_returns(inner) {
+                    self.merge(&after_then);
+                }
+            }
+            IRNode::Loop(ref cond, ref inner, _) => {
+                self.check_condition(cond);
+
+                // The Loop might not run at all
+                let before = self.scopes.clone();
+                self.check_block(inner, func);
+                self.merge(&before);
+            }
+            IRNode::Return(ref raw_exp, span) => {
+                let exp_type = raw_exp.as_ref().and_then(|exp| self.check_expression(exp));
+
+                match (raw_exp, &func.return_type) {
+                    (Some(_), None) => self.error(
+                        format!("Function '{}' does not return a value", func.name),
+                        *span,
+                    ),
+                    (None, Some(ret_type)) => self.error(
+                        format!("Expected a return value of type {}", ret_type),
+                        *span,
+                    ),
+                    (Some(exp), Some(ret_type)) => match exp_type {
+                        Some(exp_type) if !compatible(ret_type, &exp_type) => self.error(
+                            format!(
+                                "Expected a return value of type {}, found {}",
+                                ret_type, exp_type
+                            ),
+                            *span,
+                        ),
+                        Some(_) => self.check_fits(ret_type, exp),
+                        None => {}
+                    },
+                    (None, None) => {}
+                };
+            }
+        };
+    }
+
+    fn check_function(&mut self, func: &IRFunction) {
+        // The Labels of String-Literals are named 'STR_' followed by their Index
+        if RESERVED_NAMES.contains(&func.name.as_str()) || func.name.starts_with("STR_") {
+            self.error(
+                format!("Function name '{}' is reserved for the builtins", func.name),
+                func.span,
+            );
+        }
+        if is_assembly_keyword(&func.name) {
+            self.error(
+                format!(
+                    "Function name '{}' is reserved for the assembler",
+                    func.name
+                ),
+                func.span,
+            );
+        }
+
+        self.scopes.clear();
+        self.scopes.push(HashMap::new());
+        for param in func.parameters.iter() {
+            self.declare(&param.name, &param.param_type, true, param.span);
+        }
+
+        // The Body can shadow the Parameters, like any other nested Block
+        self.check_block(&func.statements, func);
+
+        if let Some(ref ret_type) = func.return_type {
+            if !always_returns(&func.statements) {
+                self.error(
+                    format!(
+                        "Function '{}' must return a value of type {} on every path",
+                        func.name, ret_type
+                    ),
+                    func.span,
+                );
+            }
+        }
+    }
+}
+
+/// Checks if the Statements return on every possible Path
+fn always_returns(statements: &[Vec<IRNode>]) -> bool {
+    statements.iter().flatten().any(|node| match node {
+        IRNode::Return(_, _) => true,
+        IRNode::Conditional(_, ref inner, Some(ref else_inner), _) => {
+            always_returns(inner) && always_returns(else_inner)
+        }
+        _ => false,
+    })
+}
+
+/// Checks that all the Names can be resolved and that the Types
+/// match, and returns every Error that was found or otherwise
+/// the Warnings for the Program
+pub fn check(ir: &HashMap<String, IRFunction>) -> Result<Vec<CompileWarning>, Vec<CompileError>> {
+    let mut checker = Checker {
+        functions: ir,
+        scopes: Vec::new(),
+        errors: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    // Check the Functions in the Order they appear in, to get
+    // the Errors in a consistent Order
+    let mut functions: Vec<&IRFunction> = ir.values().collect();
+    functions.sort_by_key(|func| func.span.start);
+    for func in functions {
+        checker.check_function(func);
+    }
+
+    // The Program always starts by calling 'main' without any Arguments
+    match ir.get("main") {
+        Some(main) => {
+            if !main.parameters.is_empty() {
+                checker.error("Function 'main' cant have any parameters", main.span);
+            }
+            if main.return_type.is_some() {
+                checker.error("Function 'main' cant have a return type", main.span);
+            }
+        }
+        None => checker.error("Missing function 'main'", Span::default()),
+    };
+
+    match checker.errors.is_empty() {
+        true => Ok(checker.warnings),
+        false => Err(checker.errors),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::frontend::parse;
+
+    fn check_source(content: &str) -> Result<(), Vec<String>> {
+        let ir = parse(content.to_owned()).unwrap();
+        check(&ir)
+            .map(|_| ())
+            .map_err(|errors| errors.iter().map(|e| e.message().to_owned()).collect())
+    }
+
+    #[test]
+    fn valid_program() {
+        let content = "func add(a: number, b: u8) -> number {
+  return a + b;
+}
+
+func main() {
+  number x = add(1, 2);
+  bool big = x > 2;
+  if (big) {
+    print(x);
+  }
+  print_int(x * 2);
+}
+";
+
+        assert_eq!(Ok(()), check_source(content));
+    }
+
+    #[test]
+    fn unknown_names() {
+        let content = "func main() {
+  x = 2;
+  print(y);
+  missing(1);
+}
+";
+
+        assert_eq!(
+            Err(vec![
+                "Unknown variable 'x'".to_owned(),
+                "Unknown variable 'y'".to_owned(),
+                "Unknown function 'missing'".to_owned(),
+            ]),
+            check_source(content)
+        );
+    }
+
+    #[test]
+    fn arity_and_types() {
+        let content = "func add(a: number, b: number) -> number {
+  return a + b;
+}
+
+func main() {
+  bool flag = true;
+  number x = add(1);
+  x = add(flag, 2);
+  flag = 3;
+  if (x) {
+    print(1);
+  }
+}
+";
+
+        assert_eq!(
+            Err(vec![
+                "Function 'add' expects 2 arguments, found 1".to_owned(),
+                "Expected an argument of type i64, found bool".to_owned(),
+                "Cant assign a value of type i64 to 'flag' of type bool".to_owned(),
+                "Expected a condition of type bool, found i64".to_owned(),
+            ]),
+            check_source(content)
+        );
+    }
+
+    #[test]
+    fn input_builtins() {
+        let content = "func main() {
+  number sum = 0;
+  while (!eof()) {
+    sum = sum + read_int();
+  }
+  print_int(sum);
+  bool c = read_char();
+  read_char(1);
+}
+";
+
+        assert_eq!(
+            Err(vec![
+                "Cant assign a value of type i64 to 'c' of type bool".to_owned(),
+                "Function 'read_char' expects 0 arguments, found 1".to_owned(),
+            ]),
+            check_source(content)
+        );
+    }
+
+    #[test]
+    fn division_by_zero() {
+        let content = "func id(a: number) -> number {
+  return a;
+}
+
+func main() {
+  number x = 4;
+  if (x > 2) {
+    x = x / (2 - 2);
+  }
+  print(id(x % 0));
+  x = x / 2;
+}
+";
+
+        assert_eq!(
+            Err(vec![
+                "Division by zero".to_owned(),
+                "Division by zero".to_owned(),
+            ]),
+            check_source(content)
+        );
+    }
+
+    #[test]
+    fn missing_return() {
+        let content = "func value(a: number) -> number {
+  if (a > 2) {
+    return a;
+  }
+}
+
+func nothing() {
+  return 1;
+}
+
+func main() {}
+";
+
+        assert_eq!(
+            Err(vec![
+                "Function 'value' must return a value of type i64 on every path".to_owned(),
+                "Function 'nothing' does not return a value".to_owned(),
+            ]),
+            check_source(content)
+        );
+    }
+
+    #[test]
+    fn block_scopes() {
+        let content = "func shadow(x: number) {
+  bool x = true;
+  while (x) {
+    number y = 1;
+    bool x = false;
+    number y = 2;
+  }
+  print(y);
+}
+
+func main() {}
+";
+
+        assert_eq!(
+            Err(vec![
+                "Variable 'y' is already declared".to_owned(),
+                "Unknown variable 'y'".to_owned(),
+            ]),
+            check_source(content)
+        );
+    }
+
+    #[test]
+    fn uninitialized_reads() {
+        let content = "func count(a: number) {
+  number x;
+  number y;
+  number z;
+  if (a > 0) {
+    x = 1;
+    y = 1;
+  } else {
+    x = 2;
+  }
+  while (a > 0) {
+    z = 1;
+    a = a - 1;
+  }
+  print(x + y + z);
+}
+
+func main() {}
+";
+        let ir = parse(content.to_owned()).unwrap();
+        let warnings: Vec<String> = check(&ir).unwrap().into_iter().map(|w| w.message).collect();
+
+        assert_eq!(
+            vec![
+                "Variable 'y' is used before it is initialized".to_owned(),
+                "Variable 'z' is used before it is initialized".to_owned(),
+            ],
+            warnings
+        );
+    }
+
+    #[test]
+    fn literal_ranges() {
+        let content = "func small(a: i16) -> u8 {
+  return 256;
+}
+
+func main() {
+  u8 x = 300;
+  i8 y = -129;
+  i8 z = -128;
+  u64 u = 0 - 1;
+  x = 255;
+  small(40000);
+}
+";
+        let ir = parse(content.to_owned()).unwrap();
+        let warnings: Vec<String> = check(&ir).unwrap().into_iter().map(|w| w.message).collect();
+
+        assert_eq!(
+            vec![
+                "Value 256 does not fit into u8".to_owned(),
+                "Value 300 does not fit into u8".to_owned(),
+                "Value -129 does not fit into i8".to_owned(),
+                "Value -1 does not fit into u64".to_owned(),
+                "Value 40000 does not fit into i16".to_owned(),
+            ],
+            warnings
+        );
+    }
+
+    #[test]
+    fn main_function() {
+        assert_eq!(
+            Err(vec!["Missing function 'main'".to_owned()]),
+            check_source("func notmain() {}\n")
+        );
+        assert_eq!(
+            Err(vec![
+                "Function 'main' cant have any parameters".to_owned(),
+                "Function 'main' cant have a return type".to_owned(),
+            ]),
+            check_source("func main(a: number) -> number {\n  return a;\n}\n")
+        );
+    }
+
+    #[test]
+    fn reserved_names() {
+        let content = "func print_string() {}
+func read_buffer() {}
+func STR_0() {}
+func rax() {}
+func byte() {}
+func R15D() {}
+func r16() {}
+func rdx2() {}
+func print_twice() {}
+func main() {}
+";
+
+        assert_eq!(
+            Err(vec![
+                "Function name 'print_string' is reserved for the builtins".to_owned(),
+                "Function name 'read_buffer' is reserved for the builtins".to_owned(),
+                "Function name 'STR_0' is reserved for the builtins".to_owned(),
+                "Function name 'rax' is reserved for the assembler".to_owned(),
+                "Function name 'byte' is reserved for the assembler".to_owned(),
+                "Function name 'R15D' is reserved for the assembler".to_owned(),
+            ]),
+            check_source(content)
+        );
+    }
+
+    #[test]
+    fn strings() {
+        let content = "func greet(name: string) {
+  print(name);
+}
+
+func main() {
+  string s = \"hi\";
+  greet(s);
+  greet(1);
+  print(s == s);
+}
+";
+
+        assert_eq!(
+            Err(vec![
+                "Expected an argument of type string, found i64".to_owned(),
+                "Cant compare string with string".to_owned(),
+                "Expected an argument of type i64, found bool".to_owned(),
+            ]),
+            check_source(content)
+        );
+    }
+}
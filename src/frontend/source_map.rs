@@ -0,0 +1,77 @@
+use super::Span;
+
+/// A single Source-File that is part of the Program
+#[derive(Debug, PartialEq, Clone)]
+pub struct SourceFile {
+    pub name: String,
+    pub content: String,
+    /// The Offset of the first Byte of this File in the entire Program
+    pub offset: usize,
+}
+
+/// Keeps track of all the Source-Files that make up a Program.
+///
+/// Every File gets its own Range of Byte-Offsets, so a Span can
+/// always be traced back to the File it came from
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add<N: Into<String>>(&mut self, name: N, content: String) -> &SourceFile {
+        // Leave a gap of one Byte between Files, so that the End-Of-File
+        // Span of one File can not be confused with the start of the next one
+        let offset = match self.files.last() {
+            Some(last) => last.offset + last.content.len() + 1,
+            None => 0,
+        };
+
+        self.files.push(SourceFile {
+            name: name.into(),
+            content,
+            offset,
+        });
+        self.files.last().unwrap()
+    }
+
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    /// Finds the File that contains the given Span
+    pub fn lookup(&self, span: &Span) -> Option<&SourceFile> {
+        self.files
+            .iter()
+            .rev()
+            .find(|file| file.offset <= span.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_files() {
+        let mut map = SourceMap::new();
+        map.add("first.cl", "func a() {}".to_owned());
+        map.add("second.cl", "func b() {}".to_owned());
+
+        assert_eq!(12, map.files()[1].offset);
+        assert_eq!(
+            Some("first.cl"),
+            map.lookup(&Span::new(11, 11, 1, 12))
+                .map(|f| f.name.as_str())
+        );
+        assert_eq!(
+            Some("second.cl"),
+            map.lookup(&Span::new(17, 18, 1, 6))
+                .map(|f| f.name.as_str())
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+/// The Location of some Part of the Source-Code
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Span {
+    /// The Byte-Offset of the first Character
+    pub start: usize,
+    /// The Byte-Offset right after the last Character
+    pub end: usize,
+    /// The Line of the first Character, starting at 1
+    pub line: usize,
+    /// The Column of the first Character, starting at 1
+    pub column: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize, line: usize, column: usize) -> Self {
+        Self {
+            start,
+            end,
+            line,
+            column,
+        }
+    }
+
+    /// Creates a new Span that covers both this and the other Span
+    /// as well as everything in between
+    pub fn merge(&self, other: &Span) -> Span {
+        let first = if self.start <= other.start {
+            self
+        } else {
+            other
+        };
+
+        Span {
+            start: first.start,
+            end: self.end.max(other.end),
+            line: first.line,
+            column: first.column,
+        }
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_ordered() {
+        let first = Span::new(0, 3, 1, 1);
+        let second = Span::new(5, 8, 1, 6);
+
+        assert_eq!(Span::new(0, 8, 1, 1), first.merge(&second));
+    }
+
+    #[test]
+    fn merge_reversed() {
+        let first = Span::new(10, 12, 2, 3);
+        let second = Span::new(0, 3, 1, 1);
+
+        assert_eq!(Span::new(0, 12, 1, 1), first.merge(&second));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod backend;
+pub mod error;
 pub mod frontend;
 pub mod optimizer;
@@ -1,25 +1,140 @@
 use facompiler::{
     backend::{self, traits::Arch},
-    frontend, optimizer,
+    error::{CompileError, CompileWarning},
+    frontend::{
+        self,
+        diagnostics::{self, Diagnostic},
+        SourceMap,
+    },
+    optimizer,
 };
 
-use env_logger;
+mod cli;
+
+/// Runs the Compilation up until the requested Stage and returns the
+/// Output of that Stage, while collecting all the Warnings
+fn compile(
+    options: &cli::Options,
+    sources: &SourceMap,
+    warnings: &mut Vec<CompileWarning>,
+) -> Result<String, Vec<CompileError>> {
+    if options.emit == cli::Emit::Tokens {
+        let mut result = String::new();
+        for file in sources.files() {
+            for token in frontend::tokenize(file).map_err(|e| vec![e])? {
+                result.push_str(&format!(
+                    "{}:{}\t{:?}\n",
+                    file.name, token.span, token.token
+                ));
+            }
+        }
+        return Ok(result);
+    }
+
+    // Parse the content to IR
+    let mut ir = frontend::parse_files(sources).map_err(|e| vec![e])?;
+    if options.emit == cli::Emit::IR {
+        return Ok(frontend::ir::pretty_format(&ir));
+    }
+
+    // Check the Names and Types
+    warnings.append(&mut frontend::sema::check(&ir)?);
+
+    // Optimize it
+    optimizer::optimize_with_hook(&mut ir, options.opt_level, |pass, ir| {
+        if options.dump_ir_after == Some(pass) {
+            eprintln!("IR after '{}':", pass.name());
+            eprint!("{}", frontend::ir::pretty_format(ir));
+        }
+    })
+    .map_err(|e| vec![e])?;
+    if options.emit == cli::Emit::OptimizedIR {
+        return Ok(frontend::ir::pretty_format(&ir));
+    }
+
+    // Generate Assembly from the IR
+    backend::archs::x86_64::X86_64::generate_asm_string(ir).map_err(|e| vec![e])
+}
+
+/// The Path the Assembly is written to, if no explicit Output was given
+fn default_output(input: &str) -> String {
+    std::path::Path::new(input)
+        .with_extension("asm")
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Prints the Diagnostic together with the Source-Code it points at
+fn report(sources: &SourceMap, diagnostic: Diagnostic) {
+    let (name, content) = match sources.lookup(&diagnostic.span) {
+        Some(file) => (file.name.as_str(), file.content.as_str()),
+        None => ("<unknown>", ""),
+    };
+    eprint!(
+        "{}",
+        diagnostic.render(name, content, diagnostics::use_colour())
+    );
+}
 
 fn main() {
     env_logger::init();
 
-    let file_path = "./examples/hello_world.cl";
+    let options = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(cli::Command::Compile(options)) => options,
+        Ok(cli::Command::Help) => {
+            print!("{}", cli::USAGE);
+            return;
+        }
+        Err(message) => {
+            eprintln!("error: {}\n", message);
+            eprint!("{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
 
-    let content = std::fs::read_to_string(file_path).unwrap();
+    let mut sources = SourceMap::new();
+    for input in options.inputs.iter() {
+        match std::fs::read_to_string(input) {
+            Ok(content) => sources.add(input.as_str(), content),
+            Err(e) => {
+                eprintln!("error: Could not read '{}': {}", input, e);
+                std::process::exit(1);
+            }
+        };
+    }
 
-    // Parse the content to IR
-    let mut ir = frontend::parse(content);
+    let mut warnings = Vec::new();
+    let result = compile(&options, &sources, &mut warnings);
 
-    // Optimize it
-    optimizer::optimize(&mut ir);
+    for warning in warnings.iter() {
+        report(&sources, Diagnostic::from(warning));
+    }
 
-    // Generate Assembly from the IR
-    let asm = backend::archs::x86_64::X86_64::generate_asm_string(ir);
+    let output = match result {
+        Ok(output) => output,
+        Err(errors) => {
+            for err in errors.iter() {
+                report(&sources, Diagnostic::from(err));
+            }
+            std::process::exit(1);
+        }
+    };
+
+    let output_path = match (&options.output, options.emit) {
+        (Some(path), _) => path.clone(),
+        (None, cli::Emit::Asm) => default_output(&options.inputs[0]),
+        (None, _) => {
+            print!("{}", output);
+            return;
+        }
+    };
+
+    if let Err(e) = std::fs::write(&output_path, output) {
+        eprintln!("error: Could not write '{}': {}", output_path, e);
+        std::process::exit(1);
+    }
 
-    std::fs::write("./test.asm", asm).expect("Unable to write asm file");
+    if !options.quiet {
+        println!("Wrote {}", output_path);
+    }
 }
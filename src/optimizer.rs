@@ -1,13 +1,96 @@
-use crate::frontend::{self, ir::IRFunction};
+use crate::{error::CompileError, frontend::ir::IRFunction};
 
 mod const_prop;
 
-pub fn optimize(ir: &mut std::collections::HashMap<String, IRFunction>) {
-    // Do some optimization
-    for (_, func) in ir.iter_mut() {
-        const_prop::propagate(func);
+/// Controls which Optimizations are run on the IR
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum OptLevel {
+    /// Don't run any Optimizations
+    O0,
+    /// Run the cheap Optimizations, like Constant-Propagation
+    #[default]
+    O1,
+    /// Run all the available Optimizations, which currently
+    /// are the same as for O1
+    O2,
+}
+
+/// A single Optimization-Pass over the IR
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Pass {
+    ConstProp,
+}
+
+impl Pass {
+    /// The Name used to refer to the Pass, for example on the Command-Line
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pass::ConstProp => "const-prop",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Pass> {
+        match name {
+            "const-prop" => Some(Pass::ConstProp),
+            _ => None,
+        }
+    }
+}
+
+/// The Passes that are run for the given Level, in Order
+pub fn passes(level: OptLevel) -> &'static [Pass] {
+    match level {
+        OptLevel::O0 => &[],
+        OptLevel::O1 | OptLevel::O2 => &[Pass::ConstProp],
+    }
+}
+
+pub fn optimize(
+    ir: &mut std::collections::HashMap<String, IRFunction>,
+    level: OptLevel,
+) -> Result<(), CompileError> {
+    optimize_with_hook(ir, level, |_, _| {})
+}
+
+/// Optimizes the IR and calls the Hook after every Pass, which
+/// can be used to inspect the IR for debugging
+pub fn optimize_with_hook<F>(
+    ir: &mut std::collections::HashMap<String, IRFunction>,
+    level: OptLevel,
+    mut after_pass: F,
+) -> Result<(), CompileError>
+where
+    F: FnMut(Pass, &std::collections::HashMap<String, IRFunction>),
+{
+    for pass in passes(level) {
+        for func in ir.values_mut() {
+            match pass {
+                Pass::ConstProp => const_prop::propagate(func)?,
+            };
+        }
+
+        after_pass(*pass, ir);
     }
 
-    println!("Optimized");
-    frontend::ir::pretty_print(&ir);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::frontend::parse;
+
+    #[test]
+    fn hook_after_every_pass() {
+        let mut ir = parse("func main() {\n  print(2);\n}\n".to_owned()).unwrap();
+
+        let mut called = Vec::new();
+        optimize_with_hook(&mut ir, OptLevel::O1, |pass, _| called.push(pass)).unwrap();
+        assert_eq!(vec![Pass::ConstProp], called);
+
+        called.clear();
+        optimize_with_hook(&mut ir, OptLevel::O0, |pass, _| called.push(pass)).unwrap();
+        assert_eq!(Vec::<Pass>::new(), called);
+    }
 }
@@ -1,104 +1,145 @@
-use crate::frontend::ir::{IRExpression, IRFunction, IRNode, IROperation, IRValue};
+use crate::{
+    error::CompileError,
+    frontend::{
+        ir::{IRExpression, IRFunction, IRNode, IROperation, IRValue},
+        Span,
+    },
+};
 
-fn combine_two(op: &IROperation, v1: &IRValue, v2: &IRValue) -> IRExpression {
+fn combine_two(
+    op: &IROperation,
+    v1: &IRValue,
+    v2: &IRValue,
+    span: Span,
+) -> Result<IRExpression, CompileError> {
     match (v1, v2) {
         (IRValue::Number(n1), IRValue::Number(n2)) => {
+            // All the Operations wrap around on Overflow, the same
+            // as they do at runtime
             let result = match op {
-                IROperation::Add => n1 + n2,
-                IROperation::Sub => n1 - n2,
-                IROperation::Divide => n1 / n2,
-                IROperation::Multiply => n1 * n2,
+                IROperation::Add => n1.wrapping_add(*n2),
+                IROperation::Sub => n1.wrapping_sub(*n2),
+                IROperation::Multiply => n1.wrapping_mul(*n2),
+                // This is already reported by the Semantic-Checks, so the
+                // Operation is just left as it is
+                IROperation::Divide | IROperation::Modulo if *n2 == 0 => {
+                    return Ok(IRExpression::Operation(
+                        op.clone(),
+                        vec![
+                            IRExpression::Value(v1.clone(), span),
+                            IRExpression::Value(v2.clone(), span),
+                        ],
+                        span,
+                    ));
+                }
+                IROperation::Divide => n1.wrapping_div(*n2),
+                IROperation::Modulo => n1.wrapping_rem(*n2),
             };
 
-            IRExpression::Value(IRValue::Number(result))
+            Ok(IRExpression::Value(IRValue::Number(result), span))
         }
+        (_, _) => Err(CompileError::semantic(
+            format!("Cant use {:?} on values that are not numbers", op),
+            span,
+        )),
     }
 }
 
-fn propagate_exp(exp: &IRExpression) -> IRExpression {
+fn propagate_exp(exp: &IRExpression) -> Result<IRExpression, CompileError> {
     match exp {
-        IRExpression::Operation(ref op, ref expressions) => {
-            let first = expressions.get(0).unwrap();
+        IRExpression::Operation(ref op, ref expressions, span) => {
+            let span = *span;
+            let first = expressions.first().unwrap();
             let second = expressions.get(1).unwrap();
             match (first, second) {
-                (IRExpression::Value(v1), IRExpression::Value(v2)) => combine_two(op, v1, v2),
-                (IRExpression::Value(v1), IRExpression::Operation(_, _)) => {
-                    let v2 = match propagate_exp(second) {
-                        IRExpression::Value(t) => t,
-                        _ => return exp.clone(),
+                (IRExpression::Value(v1, _), IRExpression::Value(v2, _)) => {
+                    combine_two(op, v1, v2, span)
+                }
+                (IRExpression::Value(v1, _), IRExpression::Operation(_, _, _)) => {
+                    let v2 = match propagate_exp(second)? {
+                        IRExpression::Value(t, _) => t,
+                        _ => return Ok(exp.clone()),
                     };
 
-                    combine_two(op, v1, &v2)
+                    combine_two(op, v1, &v2, span)
                 }
-                (IRExpression::Operation(_, _), IRExpression::Value(v2)) => {
-                    let v1 = match propagate_exp(first) {
-                        IRExpression::Value(t) => t,
-                        _ => return exp.clone(),
+                (IRExpression::Operation(_, _, _), IRExpression::Value(v2, _)) => {
+                    let v1 = match propagate_exp(first)? {
+                        IRExpression::Value(t, _) => t,
+                        _ => return Ok(exp.clone()),
                     };
 
-                    combine_two(op, &v1, v2)
+                    combine_two(op, &v1, v2, span)
                 }
-                (IRExpression::Operation(_, _), IRExpression::Operation(_, _)) => {
-                    let v1 = match propagate_exp(first) {
-                        IRExpression::Value(t) => t,
-                        _ => return exp.clone(),
+                (IRExpression::Operation(_, _, _), IRExpression::Operation(_, _, _)) => {
+                    let v1 = match propagate_exp(first)? {
+                        IRExpression::Value(t, _) => t,
+                        _ => return Ok(exp.clone()),
                     };
 
-                    let v2 = match propagate_exp(second) {
-                        IRExpression::Value(t) => t,
-                        _ => return exp.clone(),
+                    let v2 = match propagate_exp(second)? {
+                        IRExpression::Value(t, _) => t,
+                        _ => return Ok(exp.clone()),
                     };
 
-                    combine_two(op, &v1, &v2)
+                    combine_two(op, &v1, &v2, span)
                 }
-                (_, IRExpression::Operation(_, _)) => {
-                    let value = match propagate_exp(second) {
-                        IRExpression::Value(t) => t,
-                        _ => return exp.clone(),
+                (_, IRExpression::Operation(_, _, _)) => {
+                    let value = match propagate_exp(second)? {
+                        value @ IRExpression::Value(_, _) => value,
+                        _ => return Ok(exp.clone()),
                     };
 
-                    IRExpression::Operation(
+                    Ok(IRExpression::Operation(
                         op.clone(),
-                        vec![first.clone(), IRExpression::Value(value)],
-                    )
+                        vec![first.clone(), value],
+                        span,
+                    ))
                 }
-                (IRExpression::Operation(_, _), _) => {
-                    let value = match propagate_exp(first) {
-                        IRExpression::Value(t) => t,
-                        _ => return exp.clone(),
+                (IRExpression::Operation(_, _, _), _) => {
+                    let value = match propagate_exp(first)? {
+                        value @ IRExpression::Value(_, _) => value,
+                        _ => return Ok(exp.clone()),
                     };
 
-                    IRExpression::Operation(
+                    Ok(IRExpression::Operation(
                         op.clone(),
-                        vec![IRExpression::Value(value), second.clone()],
-                    )
+                        vec![value, second.clone()],
+                        span,
+                    ))
                 }
-                (_, _) => exp.clone(),
+                (_, _) => Ok(exp.clone()),
             }
         }
-        _ => exp.clone(),
+        _ => Ok(exp.clone()),
     }
 }
 
-pub fn propagate(ir: &mut IRFunction) {
+pub fn propagate(ir: &mut IRFunction) -> Result<(), CompileError> {
     for statement in ir.statements.iter_mut() {
         for part in statement.iter_mut() {
             match part {
-                IRNode::Assignment(_, exp) => {
-                    let n_exp = propagate_exp(exp);
+                IRNode::Assignment(_, exp, _) => {
+                    let n_exp = propagate_exp(exp)?;
+                    drop(std::mem::replace(exp, n_exp));
+                }
+                IRNode::SingleExpression(exp, _) => {
+                    let n_exp = propagate_exp(exp)?;
                     drop(std::mem::replace(exp, n_exp));
                 }
-                IRNode::SingleExpression(exp) => {
-                    let n_exp = propagate_exp(exp);
+                IRNode::DeclareVariable(_, _, Some(exp), _) => {
+                    let n_exp = propagate_exp(exp)?;
                     drop(std::mem::replace(exp, n_exp));
                 }
-                IRNode::DeclareVariable(_, _) => {}
-                IRNode::Conditional(_, _) => {}
-                IRNode::Loop(_, _) => {}
-                IRNode::Return(_) => {}
+                IRNode::DeclareVariable(_, _, None, _) => {}
+                IRNode::Conditional(_, _, _, _) => {}
+                IRNode::Loop(_, _, _) => {}
+                IRNode::Return(_, _) => {}
             };
         }
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -111,13 +152,14 @@ mod tests {
         let exp = IRExpression::Operation(
             IROperation::Add,
             vec![
-                IRExpression::Value(IRValue::Number(1)),
-                IRExpression::Value(IRValue::Number(2)),
+                IRExpression::Value(IRValue::Number(1), Span::default()),
+                IRExpression::Value(IRValue::Number(2), Span::default()),
             ],
+            Span::default(),
         );
-        let expected = IRExpression::Value(IRValue::Number(3));
+        let expected = IRExpression::Value(IRValue::Number(3), Span::default());
 
-        assert_eq!(expected, propagate_exp(&exp));
+        assert_eq!(Ok(expected), propagate_exp(&exp));
     }
 
     #[test]
@@ -125,19 +167,21 @@ mod tests {
         let exp = IRExpression::Operation(
             IROperation::Add,
             vec![
-                IRExpression::Value(IRValue::Number(1)),
+                IRExpression::Value(IRValue::Number(1), Span::default()),
                 IRExpression::Operation(
                     IROperation::Add,
                     vec![
-                        IRExpression::Value(IRValue::Number(2)),
-                        IRExpression::Value(IRValue::Number(3)),
+                        IRExpression::Value(IRValue::Number(2), Span::default()),
+                        IRExpression::Value(IRValue::Number(3), Span::default()),
                     ],
+                    Span::default(),
                 ),
             ],
+            Span::default(),
         );
-        let expected = IRExpression::Value(IRValue::Number(6));
+        let expected = IRExpression::Value(IRValue::Number(6), Span::default());
 
-        assert_eq!(expected, propagate_exp(&exp));
+        assert_eq!(Ok(expected), propagate_exp(&exp));
     }
     #[test]
     fn nested_propagate_as_first() {
@@ -147,16 +191,18 @@ mod tests {
                 IRExpression::Operation(
                     IROperation::Add,
                     vec![
-                        IRExpression::Value(IRValue::Number(2)),
-                        IRExpression::Value(IRValue::Number(3)),
+                        IRExpression::Value(IRValue::Number(2), Span::default()),
+                        IRExpression::Value(IRValue::Number(3), Span::default()),
                     ],
+                    Span::default(),
                 ),
-                IRExpression::Value(IRValue::Number(1)),
+                IRExpression::Value(IRValue::Number(1), Span::default()),
             ],
+            Span::default(),
         );
-        let expected = IRExpression::Value(IRValue::Number(6));
+        let expected = IRExpression::Value(IRValue::Number(6), Span::default());
 
-        assert_eq!(expected, propagate_exp(&exp));
+        assert_eq!(Ok(expected), propagate_exp(&exp));
     }
 
     #[test]
@@ -167,21 +213,90 @@ mod tests {
                 IRExpression::Operation(
                     IROperation::Add,
                     vec![
-                        IRExpression::Value(IRValue::Number(2)),
-                        IRExpression::Value(IRValue::Number(5)),
+                        IRExpression::Value(IRValue::Number(2), Span::default()),
+                        IRExpression::Value(IRValue::Number(5), Span::default()),
                     ],
+                    Span::default(),
                 ),
                 IRExpression::Operation(
                     IROperation::Add,
                     vec![
-                        IRExpression::Value(IRValue::Number(1)),
-                        IRExpression::Value(IRValue::Number(2)),
+                        IRExpression::Value(IRValue::Number(1), Span::default()),
+                        IRExpression::Value(IRValue::Number(2), Span::default()),
                     ],
+                    Span::default(),
                 ),
             ],
+            Span::default(),
         );
-        let expected = IRExpression::Value(IRValue::Number(10));
+        let expected = IRExpression::Value(IRValue::Number(10), Span::default());
+
+        assert_eq!(Ok(expected), propagate_exp(&exp));
+    }
 
-        assert_eq!(expected, propagate_exp(&exp));
+    #[test]
+    fn division_by_zero() {
+        let exp = IRExpression::Operation(
+            IROperation::Divide,
+            vec![
+                IRExpression::Value(IRValue::Number(2), Span::default()),
+                IRExpression::Value(IRValue::Number(0), Span::default()),
+            ],
+            Span::default(),
+        );
+
+        assert_eq!(Ok(exp.clone()), propagate_exp(&exp));
+    }
+
+    #[test]
+    fn modulo() {
+        let exp = IRExpression::Operation(
+            IROperation::Modulo,
+            vec![
+                IRExpression::Value(IRValue::Number(7), Span::default()),
+                IRExpression::Value(IRValue::Number(3), Span::default()),
+            ],
+            Span::default(),
+        );
+
+        assert_eq!(
+            Ok(IRExpression::Value(IRValue::Number(1), Span::default())),
+            propagate_exp(&exp)
+        );
+    }
+
+    #[test]
+    fn wrapping() {
+        let exp = IRExpression::Operation(
+            IROperation::Sub,
+            vec![
+                IRExpression::Value(IRValue::Number(i64::MIN), Span::default()),
+                IRExpression::Value(IRValue::Number(1), Span::default()),
+            ],
+            Span::default(),
+        );
+        assert_eq!(
+            Ok(IRExpression::Value(
+                IRValue::Number(i64::MAX),
+                Span::default()
+            )),
+            propagate_exp(&exp)
+        );
+
+        let exp = IRExpression::Operation(
+            IROperation::Divide,
+            vec![
+                IRExpression::Value(IRValue::Number(i64::MIN), Span::default()),
+                IRExpression::Value(IRValue::Number(-1), Span::default()),
+            ],
+            Span::default(),
+        );
+        assert_eq!(
+            Ok(IRExpression::Value(
+                IRValue::Number(i64::MIN),
+                Span::default()
+            )),
+            propagate_exp(&exp)
+        );
     }
 }